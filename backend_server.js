@@ -36,6 +36,7 @@ const PORT = 3000;
 const CONTRACT_ID = 'CDXYPMRADZYRBUI2GNYICZBS7H6GJLX7BI3DI4HCUG2YISRR3NTLL4R4';
 const ADMIN_ACCOUNT = 'alice';
 const NETWORK = 'testnet';
+const TOKEN_ADDRESS = 'CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC';
 
 // Middleware
 app.use(cors());
@@ -91,6 +92,7 @@ app.post('/api/add-employee', async (req, res) => {
   --source ${ADMIN_ACCOUNT} \
   --network ${NETWORK} \
   -- add_employee \
+  --caller ${ADMIN_ACCOUNT} \
   --employee_address "${address}" \
   --name "${name}" \
   --base_salary "${annualStroops}"`;
@@ -140,7 +142,8 @@ app.get('/api/treasury-balance', async (req, res) => {
   --id ${CONTRACT_ID} \
   --source ${ADMIN_ACCOUNT} \
   --network ${NETWORK} \
-  -- get_treasury_balance`;
+  -- get_treasury_balance \
+  --token ${TOKEN_ADDRESS}`;
 
         const { stdout } = await execAsync(command);
         const balance = stdout.trim().replace(/"/g, '');
@@ -254,6 +257,7 @@ app.post('/api/pay-all-salaries', async (req, res) => {
   --source ${ADMIN_ACCOUNT} \
   --network ${NETWORK} \
   -- pay_salary \
+  --caller ${ADMIN_ACCOUNT} \
   --employee_address "${employeeAddress}"`;
 
                 const { stdout: payOutput } = await execAsync(payCommand);
@@ -371,6 +375,7 @@ app.post('/api/fund-treasury', async (req, res) => {
   --network ${NETWORK} \
   -- fund_treasury \
   --from "${aliceAddress}" \
+  --token ${TOKEN_ADDRESS} \
   --amount "${amountStroops}"`;
 
         const { stdout, stderr } = await execAsync(command);
@@ -437,6 +442,7 @@ app.post('/api/stop-employee-salary', async (req, res) => {
   --source ${ADMIN_ACCOUNT} \
   --network ${NETWORK} \
   -- stop_employee_salary \
+  --caller ${ADMIN_ACCOUNT} \
   --employee_address "${employeeAddress}"`;
 
         const { stdout, stderr } = await execAsync(command);
@@ -501,6 +507,7 @@ app.post('/api/resume-employee-salary', async (req, res) => {
   --source ${ADMIN_ACCOUNT} \
   --network ${NETWORK} \
   -- resume_employee_salary \
+  --caller ${ADMIN_ACCOUNT} \
   --employee_address "${employeeAddress}"`;
 
         const { stdout, stderr } = await execAsync(command);
@@ -567,6 +574,7 @@ app.post('/api/claim-salary', async (req, res) => {
   --source ${ADMIN_ACCOUNT} \
   --network ${NETWORK} \
   -- pay_salary \
+  --caller ${ADMIN_ACCOUNT} \
   --employee_address "${employeeAddress}"`;
 
         const { stdout, stderr } = await execAsync(command);
@@ -0,0 +1,104 @@
+//! Proleptic Gregorian calendar helpers for turning ledger timestamps into
+//! UTC dates. Based on Howard Hinnant's `days_from_civil` / `civil_from_days`
+//! algorithms, restricted to dates on or after the Unix epoch.
+
+pub const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Days between 0000-03-01 and 1970-01-01 in the shifted (March-based) calendar
+const EPOCH_SHIFT: u64 = 719_468;
+const DAYS_PER_ERA: u64 = 146_097;
+
+pub fn is_leap_year(year: u64) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+pub fn days_in_month(year: u64, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        _ => {
+            if is_leap_year(year) {
+                29
+            } else {
+                28
+            }
+        }
+    }
+}
+
+/// Convert days since 1970-01-01 into a (year, month, day) triple
+pub fn civil_from_days(days: u64) -> (u64, u32, u32) {
+    let z = days + EPOCH_SHIFT;
+    let era = z / DAYS_PER_ERA;
+    let doe = z - era * DAYS_PER_ERA;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month as u32, day as u32)
+}
+
+/// Convert a (year, month, day) triple into days since 1970-01-01, or `None`
+/// for an invalid date or one outside the supported range
+pub fn days_from_civil(year: u64, month: u32, day: u32) -> Option<u64> {
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    let year = if month <= 2 { year.checked_sub(1)? } else { year };
+    let era = year / 400;
+    let yoe = year - era * 400;
+    let mp = (month as u64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as u64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era.checked_mul(DAYS_PER_ERA)?
+        .checked_add(doe)?
+        .checked_sub(EPOCH_SHIFT)
+}
+
+/// UTC (year, month, day) of a ledger timestamp
+pub fn date_from_timestamp(timestamp: u64) -> (u64, u32, u32) {
+    civil_from_days(timestamp / SECONDS_PER_DAY)
+}
+
+/// Ledger timestamp of midnight UTC on the given date, or `None` if the date
+/// is invalid or has no ledger timestamp
+pub fn timestamp_from_date(year: u64, month: u32, day: u32) -> Option<u64> {
+    days_from_civil(year, month, day)?.checked_mul(SECONDS_PER_DAY)
+}
+
+/// Ledger timestamp of the `years`th anniversary of `timestamp` (at midnight
+/// UTC), or `None` past the last representable timestamp. An anniversary of
+/// February 29th falls on February 28th in common years.
+pub fn anniversary(timestamp: u64, years: u64) -> Option<u64> {
+    let (year, month, day) = date_from_timestamp(timestamp);
+    let year = year.checked_add(years)?;
+    timestamp_from_date(year, month, day.min(days_in_month(year, month)))
+}
+
+/// Whole years from `since` to `at`, counting each anniversary once its day
+/// begins
+pub fn years_between(since: u64, at: u64) -> u64 {
+    if at <= since {
+        return 0;
+    }
+    let (since_year, _, _) = date_from_timestamp(since);
+    let (year, _, _) = date_from_timestamp(at);
+    let years = year - since_year;
+    match anniversary(since, years) {
+        Some(date) if at >= date => years,
+        _ => years - 1,
+    }
+}
+
+/// Sequential month number (`year * 12 + month - 1`) used to count months
+/// between dates. Month 0 maps to December of the previous year.
+pub fn month_index(year: u64, month: u32) -> u64 {
+    year * 12 + month as u64 - 1
+}
+
+/// Inverse of [`month_index`]
+pub fn year_month_from_index(index: u64) -> (u64, u32) {
+    (index / 12, (index % 12) as u32 + 1)
+}
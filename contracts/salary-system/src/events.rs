@@ -0,0 +1,299 @@
+//! Contract events. Topics are the snake_case event name followed by any
+//! `#[topic]` fields; the remaining fields form the data map.
+
+use soroban_sdk::{contractevent, Address, String, Vec};
+
+use crate::{DeductionRule, IncrementPolicy, PayFrequency, PayoutShare, ProposalAction, Role};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Initialized {
+    #[topic]
+    pub admin: Address,
+    pub token: Address,
+    pub annual_increment_bps: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminTransferProposed {
+    #[topic]
+    pub admin: Address,
+    pub new_admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminTransferAccepted {
+    #[topic]
+    pub previous_admin: Address,
+    pub new_admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminTransferCancelled {
+    #[topic]
+    pub admin: Address,
+    pub new_admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleGranted {
+    #[topic]
+    pub account: Address,
+    pub role: Role,
+    pub admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleRevoked {
+    #[topic]
+    pub account: Address,
+    pub role: Role,
+    pub admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalCreated {
+    #[topic]
+    pub id: u32,
+    pub proposer: Address,
+    pub action: ProposalAction,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalApproved {
+    #[topic]
+    pub id: u32,
+    pub signer: Address,
+    pub approvals: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalExecuted {
+    #[topic]
+    pub id: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryWithdrawn {
+    #[topic]
+    pub token: Address,
+    #[topic]
+    pub to: Address,
+    pub amount: i128,
+    pub balance: i128,            // Treasury balance after the withdrawal
+    pub reserve: i128,            // Reserve required at the time of withdrawal
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryReconciled {
+    #[topic]
+    pub token: Address,
+    pub tracked: i128,            // Treasury balance before reconciling
+    pub actual: i128,             // Token balance held by the contract
+    pub surplus: i128,
+    pub deficit: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryFunded {
+    #[topic]
+    pub token: Address,
+    #[topic]
+    pub from: Address,
+    pub amount: i128,
+    pub balance: i128,            // Treasury balance after funding
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AcceptedTokenAdded {
+    #[topic]
+    pub token: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AcceptedTokenRemoved {
+    #[topic]
+    pub token: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmployeeAdded {
+    #[topic]
+    pub employee: Address,
+    pub name: String,
+    pub base_salary: i128,
+    pub payout_token: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SalaryPaid {
+    #[topic]
+    pub employee: Address,
+    pub payslip_id: u64,
+    pub token: Address,
+    pub periods: u32,
+    pub gross: i128,
+    pub net: i128,
+    pub paid_by: Address,
+    pub self_claim: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SalaryStopped {
+    #[topic]
+    pub employee: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SalaryResumed {
+    #[topic]
+    pub employee: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmployeeTerminated {
+    #[topic]
+    pub employee: Address,
+    pub termination_date: u64,
+    pub reason: String,
+    pub final_payslip: u64,       // 0 if nothing was owed
+    pub gross: i128,              // Final settlement including severance
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamingEnabled {
+    #[topic]
+    pub employee: Address,
+    pub accrual_checkpoint: u64,  // Timestamp accrual starts from
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayFrequencyChanged {
+    #[topic]
+    pub employee: Address,
+    pub frequency: PayFrequency,  // Frequency now in effect for the employee
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutTokenChanged {
+    #[topic]
+    pub employee: Address,
+    pub token: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutSplitChanged {
+    #[topic]
+    pub employee: Address,
+    pub split: Vec<PayoutShare>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepartmentChanged {
+    #[topic]
+    pub employee: Address,
+    pub department: String,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SalaryUpdated {
+    #[topic]
+    pub employee: Address,
+    pub base_salary: i128,
+    pub effective_from: u64,
+    pub reason: String,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IncrementPolicyChanged {
+    #[topic]
+    pub employee: Address,
+    pub policy: IncrementPolicy,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AnnualIncrementChanged {
+    pub annual_increment_bps: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MaxArrearsPeriodsChanged {
+    pub periods: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DefaultPayFrequencyChanged {
+    pub frequency: PayFrequency,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeductionsChanged {
+    pub rules: Vec<DeductionRule>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignersChanged {
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalAmountChanged {
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SalaryApprovalAmountChanged {
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReserveMonthsChanged {
+    pub months: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlConfigChanged {
+    pub threshold: u32,           // Ledgers of TTL left below which entries are extended
+    pub extend_to: u32,           // Ledgers of TTL an extension restores
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SeveranceMonthsChanged {
+    pub months: u32,
+}
@@ -0,0 +1,114 @@
+//! Fixed-point arithmetic shared by increment, deduction and payout split
+//! calculations.
+//!
+//! Rates are basis points: 1 bp = 0.01%, so `BASIS_POINTS` (10,000) is 100%.
+//! Growth factors are unsigned fixed-point numbers with 18 decimals, `SCALE`
+//! being 1.0.
+//!
+//! Rounding mode: every product rounds toward zero. `mul_div`, `apply_bps`
+//! and `scale` round once, from the exact value; `pow` rounds after each of
+//! its multiplications, so it can land below the exact power by up to
+//! `2 * exponent * exact / SCALE`. Products are computed at 256 bits so they
+//! can't overflow before the division; results that don't fit their type
+//! saturate at its maximum.
+
+pub const BASIS_POINTS: u32 = 10_000;
+pub const SCALE: u128 = 1_000_000_000_000_000_000;
+
+/// `a * b / denominator` rounded down, or `None` if the quotient doesn't fit
+/// in 128 bits or `denominator` is 0
+pub fn mul_div_floor(a: u128, b: u128, denominator: u128) -> Option<u128> {
+    if denominator == 0 {
+        return None;
+    }
+
+    let (high, low) = wide_mul(a, b);
+    if high == 0 {
+        return Some(low / denominator);
+    }
+    if high >= denominator {
+        return None;
+    }
+
+    // Long division of the 256-bit product, one bit at a time. The remainder
+    // stays below `denominator`, so shifting it overflows by at most one bit.
+    let mut remainder = high;
+    let mut quotient = 0u128;
+    for bit in (0..128).rev() {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((low >> bit) & 1);
+        quotient <<= 1;
+        if carry == 1 || remainder >= denominator {
+            remainder = remainder.wrapping_sub(denominator);
+            quotient |= 1;
+        }
+    }
+    Some(quotient)
+}
+
+/// `amount * numerator / denominator` rounded toward zero, saturating
+pub fn mul_div(amount: i128, numerator: u128, denominator: u128) -> i128 {
+    let magnitude = mul_div_floor(amount.unsigned_abs(), numerator, denominator)
+        .map_or(i128::MAX, |magnitude| magnitude.min(i128::MAX as u128) as i128);
+    if amount < 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// `basis_points` of `amount`, rounded toward zero
+pub fn apply_bps(amount: i128, basis_points: u32) -> i128 {
+    mul_div(amount, basis_points as u128, BASIS_POINTS as u128)
+}
+
+/// The factor `1 + basis_points / 10,000`, exactly
+pub fn growth_factor(basis_points: u32) -> u128 {
+    SCALE + SCALE / BASIS_POINTS as u128 * basis_points as u128
+}
+
+/// Product of two fixed-point numbers, rounded down and saturating
+pub fn mul(a: u128, b: u128) -> u128 {
+    mul_div_floor(a, b, SCALE).unwrap_or(u128::MAX)
+}
+
+/// Fixed-point `base` raised to `exponent` by squaring, in O(log exponent)
+/// products, each rounded down and saturating
+pub fn pow(base: u128, exponent: u64) -> u128 {
+    let mut result = SCALE;
+    let mut base = base;
+    let mut exponent = exponent;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul(result, base);
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            base = mul(base, base);
+        }
+    }
+    result
+}
+
+/// `amount` multiplied by a fixed-point factor, rounded toward zero
+pub fn scale(amount: i128, factor: u128) -> i128 {
+    mul_div(amount, factor, SCALE)
+}
+
+/// Full 256-bit product of `a` and `b` as (high, low) halves
+fn wide_mul(a: u128, b: u128) -> (u128, u128) {
+    const LOW_BITS: u128 = u64::MAX as u128;
+
+    let (a_high, a_low) = (a >> 64, a & LOW_BITS);
+    let (b_high, b_low) = (b >> 64, b & LOW_BITS);
+
+    let low_low = a_low * b_low;
+    let high_low = a_high * b_low;
+    let low_high = a_low * b_high;
+    let high_high = a_high * b_high;
+
+    let middle = (low_low >> 64) + (high_low & LOW_BITS) + (low_high & LOW_BITS);
+    let low = (middle << 64) | (low_low & LOW_BITS);
+    let high = high_high + (high_low >> 64) + (low_high >> 64) + (middle >> 64);
+    (high, low)
+}
@@ -0,0 +1,95 @@
+//! Annual salary increments. A policy says how fast a base salary grows
+//! (rate, compound or simple), whether it stops at a cap, and when each
+//! increment lands (every January 1st, or on each hire anniversary).
+
+use soroban_sdk::contracttype;
+
+use crate::{calendar, fixed};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum IncrementMethod {
+    Compound,                     // Each increment applies to the incremented salary
+    Simple,                       // Each increment adds the same share of the base
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum IncrementBasis {
+    CalendarYear,                 // Every January 1st after the base took effect
+    Anniversary,                  // Every anniversary of the hire date
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct IncrementPolicy {
+    pub rate: u32,                // Basis points per increment (e.g., 250 = 2.5%)
+    pub method: IncrementMethod,
+    pub cap: i128,                // Highest annual salary increments can reach (0 = uncapped)
+    pub basis: IncrementBasis,
+}
+
+impl IncrementPolicy {
+    /// Number of increments earned by `at` on a base that took effect at
+    /// `since`, for an employee hired at `hired`. Anniversaries stay those of
+    /// the hire date whenever the base changes.
+    pub fn increments_between(&self, hired: u64, since: u64, at: u64) -> u64 {
+        if at <= since {
+            return 0;
+        }
+
+        match self.basis {
+            IncrementBasis::CalendarYear => {
+                let (since_year, _, _) = calendar::date_from_timestamp(since);
+                let (year, _, _) = calendar::date_from_timestamp(at);
+                year - since_year
+            }
+            IncrementBasis::Anniversary => {
+                calendar::years_between(hired, at) - calendar::years_between(hired, since)
+            }
+        }
+    }
+
+    /// First time after `at` the salary on a base that took effect at `since`
+    /// changes because of an increment, for an employee hired at `hired`
+    /// (`u64::MAX` if that is past the last ledger timestamp)
+    pub fn next_increment_after(&self, hired: u64, since: u64, at: u64) -> u64 {
+        match self.basis {
+            IncrementBasis::CalendarYear => {
+                let (year, _, _) = calendar::date_from_timestamp(at.max(since));
+                calendar::timestamp_from_date(year + 1, 1, 1).unwrap_or(u64::MAX)
+            }
+            IncrementBasis::Anniversary => calendar::anniversary(
+                hired,
+                calendar::years_between(hired, at.max(since)) + 1,
+            )
+            .unwrap_or(u64::MAX),
+        }
+    }
+
+    /// Annual salary after `increments` increments on `base_salary`, never
+    /// above the exact value. Simple increments round down once; compound
+    /// ones scale the base by a growth factor that `fixed::pow` rounds at
+    /// every multiplication, which for salaries up to 10^12 and 14 increments
+    /// still lands at most one unit below. The result never exceeds the cap,
+    /// but a base already above it is kept.
+    pub fn apply(&self, base_salary: i128, increments: u64) -> i128 {
+        let salary = match self.method {
+            IncrementMethod::Compound => fixed::scale(
+                base_salary,
+                fixed::pow(fixed::growth_factor(self.rate), increments),
+            ),
+            IncrementMethod::Simple => base_salary.saturating_add(fixed::mul_div(
+                base_salary,
+                self.rate as u128 * increments as u128,
+                fixed::BASIS_POINTS as u128,
+            )),
+        };
+
+        if self.cap > 0 && salary > self.cap {
+            self.cap.max(base_salary)
+        } else {
+            salary
+        }
+    }
+}
@@ -1,10 +1,22 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, token, Address, Env, String, Vec,
+    contract, contracterror, contractimpl, contracttype, token, vec, Address, BytesN, Env,
+    String, Vec,
 };
 
-#[derive(Clone)]
+pub mod calendar;
+pub mod events;
+pub mod fixed;
+mod increment;
+mod period;
+
+pub use increment::{IncrementBasis, IncrementMethod, IncrementPolicy};
+pub use period::PayFrequency;
+
+use fixed::BASIS_POINTS;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct Employee {
     pub address: Address,
@@ -12,20 +24,344 @@ pub struct Employee {
     pub base_salary: i128,        // Annual base salary
     pub start_year: u64,          // Year when employee started
     pub start_month: u32,         // Month when employee started (1-12)
-    pub last_payment_year: u64,   // Last year payment was made
-    pub last_payment_month: u32,  // Last month payment was made (1-12)
+    pub last_payment_year: u64,   // Year of the last paid period
+    pub last_payment_month: u32,  // Month of the last paid period (1-12)
     pub is_active: bool,          // Whether employee can receive salary
+    pub suspended_at: u64,        // Timestamp when salary was last stopped
+    pub inactive_spans: Vec<InactiveSpan>, // Unpaid stretches the employee was inactive
+    pub start_timestamp: u64,     // Ledger timestamp when employee was added
+    pub streaming: bool,          // Salary accrues per second instead of monthly
+    pub accrual_checkpoint: u64,  // Timestamp up to which accrual is settled
+    pub accrued_balance: i128,    // Settled accrual not yet withdrawn
+    pub paid_through: u64,        // End of the last paid period (0 if never paid)
+    pub payout_token: Address,    // Token the salary is paid in
+    pub department: String,       // Empty until assigned
+}
+
+/// Employee record of deployments that kept employees in instance storage,
+/// converted by `migrate_employees`
+#[derive(Clone)]
+#[contracttype]
+pub struct LegacyEmployee {
+    pub address: Address,
+    pub name: String,
+    pub base_salary: i128,
+    pub start_year: u64,
+    pub start_month: u32,
+    pub last_payment_year: u64,
+    pub last_payment_month: u32,  // 0 if never paid
+    pub is_active: bool,
+}
+
+/// Half-open `[from, to)` timestamp range during which salary was stopped
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct InactiveSpan {
+    pub from: u64,
+    pub to: u64,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum StatusFilter {
+    Any,
+    Active,
+    Inactive,
+}
+
+/// Criteria for `list_employees` and `count_employees`; every criterion must match
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct EmployeeFilter {
+    pub status: StatusFilter,
+    pub department: String,       // Empty matches every department
+    pub hired_from_year: u64,     // Inclusive lower bound on start year (0 for none)
+    pub hired_to_year: u64,       // Inclusive upper bound on start year (0 for none)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct EmployeePage {
+    pub employees: Vec<Employee>,
+    pub next_cursor: Option<u32>, // Cursor for the next page, None when finished
+}
+
+/// Running state of `project_sustainability`, passed from one batch of
+/// employees to the next
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SustainabilityProjection {
+    pub as_of: u64,               // Ledger timestamp the projection started at
+    pub tokens: Vec<Address>,     // Payout tokens of the employees included so far
+    pub costs: Vec<Vec<i128>>,    // For each year covered so far, the salary cost per token
+    pub years: u32,               // Years every bucket covers the employees included so far
+    pub next_cursor: Option<u32>, // Next employee position, None once the result is known
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct EmployeeTally {
+    pub matching: u32,            // Matching employees among the positions scanned
+    pub next_cursor: Option<u32>, // Cursor for the next batch, None when finished
+}
+
+/// Archived record of an employee taken off the payroll
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Termination {
+    pub employee: Employee,       // Record as of the final settlement
+    pub termination_date: u64,    // Timestamp employment ended
+    pub reason: String,
+    pub terminated_at: u64,       // Ledger timestamp of the termination
+    pub final_payslip: u64,       // Payslip id of the final settlement (0 if nothing was owed)
+}
+
+/// Change to an employee's base annual salary, in force from the first of
+/// its effective month
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SalaryChange {
+    pub base_salary: i128,        // New annual salary before later increments
+    pub effective_from: u64,      // Timestamp of the first day of the effective month
+    pub reason: String,
+    pub recorded_at: u64,         // Ledger timestamp the change was made
+}
+
+/// Share of each salary payment routed to `destination`, in basis points
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PayoutShare {
+    pub destination: Address,
+    pub basis_points: u32,        // 10000 = the whole payment
+}
+
+/// Salary owed to an employee for pay periods that have not been paid yet
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Arrears {
+    pub periods: u32,
+    pub amount: i128,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum DeductionKind {
+    Percentage(u32),              // Basis points of gross pay
+    Fixed(i128),                  // Amount per pay period, in the payout token
+}
+
+/// Withholding taken from gross pay and sent to `destination` (tax authority,
+/// pension fund, insurer)
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct DeductionRule {
+    pub name: String,
+    pub kind: DeductionKind,
+    pub destination: Address,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct DeductionLine {
+    pub name: String,
+    pub destination: Address,
+    pub amount: i128,
+}
+
+/// Record of a salary payment, kept so employees can prove their income history
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Payslip {
+    pub id: u64,
+    pub employee: Address,
+    pub periods: u32,             // Pay periods settled (0 for streamed withdrawals)
+    pub period_start: u64,        // Start of the first period paid (withdrawal time when streamed)
+    pub period_end: u64,          // End of the last period paid (withdrawal time when streamed)
+    pub gross: i128,              // Amount debited from the treasury
+    pub deductions: Vec<DeductionLine>,
+    pub net: i128,                // Amount paid to the employee's payout destinations
+    pub token: Address,
+    pub paid_at: u64,             // Ledger timestamp of the payment
+    pub paid_by: Address,         // Payroll operator, or the employee for self-claims
+    pub self_claim: bool,
+}
+
+/// Duties that can be delegated by the admin. The admin holds every role.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum Role {
+    Hr,                           // Onboards employees and manages their salary status
+    Treasurer,                    // Moves treasury funds
+    PayrollOperator,              // Triggers salary payments
+    Auditor,                      // Reconciles the treasury with the contract's token balances
+}
+
+/// Change that can only be made with approval from M of N signers
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum ProposalAction {
+    SetAnnualIncrement(u32),      // Basis points
+    SetSigners(Vec<Address>, u32), // New signer set and approval threshold
+    SetApprovalAmount(i128),
+    SetReserveMonths(u32),
+    WithdrawTreasury(Address, Address, i128), // Token, recipient and amount
+    SetSalaryApprovalAmount(i128),
+    AddEmployee(Address, String, i128), // Address, name and annual base salary
+    SetSeveranceMonths(u32),
+    SetDeductions(Vec<DeductionRule>),
+    UpdateSalary(Address, i128, u64, u32, String), // As the `update_salary` arguments
+    SetIncrementPolicy(Address, IncrementPolicy), // Employee and policy override
+    Upgrade(BytesN<32>),          // Hash of the uploaded Wasm to run
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Proposal {
+    pub id: u32,
+    pub proposer: Address,
+    pub action: ProposalAction,
+    pub approvals: Vec<Address>,  // Signers who approved
+    pub expires_at: u64,          // Ledger timestamp after which it can't be approved or executed
+    pub executed: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum ProposalStatus {
+    Pending,                      // Awaiting approvals
+    Approved,                     // Threshold reached, ready to execute
+    Executed,
+    Expired,
+}
+
+/// Result of attempting to pay one employee during a payroll run
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum PayrollStatus {
+    Paid,
+    Inactive,
+    AlreadyPaid,
+    InsufficientTreasury,
+    Streaming,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PayrollOutcome {
+    pub employee: Address,
+    pub status: PayrollStatus,
+    pub amount: i128,             // Gross amount paid from the treasury (0 unless paid)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PayrollRun {
+    pub outcomes: Vec<PayrollOutcome>,
+    pub total_paid: i128,
+    pub next_cursor: Option<u32>, // Start index for the next batch, None when finished
+}
+
+/// Persistent entries are extended to `extend_to` ledgers once their TTL
+/// drops below `threshold`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TtlConfig {
+    pub threshold: u32,
+    pub extend_to: u32,
 }
 
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
     Admin,
-    TreasuryBalance,
-    AnnualIncrement,              // Percentage increment (e.g., 5 = 5%)
+    PendingAdmin,                 // Proposed admin awaiting acceptance
+    Treasury(Address),            // Tracked balance of a payroll token
+    AnnualIncrement,              // Increment in basis points (e.g., 500 = 5%)
     Employee(Address),
-    EmployeeList,
-    TokenAddress,
+    EmployeeList,                 // Legacy instance-storage employee list (see `migrate_employees`)
+    LegacyConfigMigrated,         // Present once a legacy deployment's configuration was converted
+    EmployeeCount,
+    EmployeeAt(u32),              // Address of the employee at a list position
+    EmployeeIndex(Address),       // List position of an employee
+    Termination(Address),         // Archived record of a terminated employee
+    TerminatedCount,
+    TerminatedAt(u32),            // Address of the employee terminated at a position, in order
+    CompensationHistory(Address), // Salary changes of an employee, by effective date
+    SeveranceMonths,              // Months of salary paid as severance on termination
+    TtlConfig,
+    TokenAddress,                 // Payout token for new employees
+    AcceptedTokens,               // Tokens the treasury can be funded and pay salaries in
+    MaxArrearsPeriods,            // Most pay periods settled by a single payment
+    PayFrequency,                 // Default pay frequency
+    EmployeePayFrequency(Address), // Per-employee pay frequency override
+    IncrementPolicy(Address),     // Per-employee increment policy override
+    PayoutSplit(Address),         // Destinations an employee's salary is split across
+    Deductions,                   // Deduction rules applied in order to gross pay
+    NextPayslipId,
+    Payslip(u64),
+    EmployeePayslips(Address),    // Payslip ids of an employee, oldest first
+    Role(Role, Address),          // Present when the address holds the role
+    Signers,                      // Multisig signers (empty when multisig is off)
+    SignerThreshold,              // Approvals required to execute a proposal
+    NextProposalId,
+    Proposal(u32),
+    ApprovalAmount,               // Withdrawals above this need a proposal under multisig
+    SalaryApprovalAmount,         // Base salaries above this need a proposal under multisig
+    ReserveMonths,                // Months of committed payroll withdrawals must leave behind
+    UntrackedDeposits(Address),   // Total surplus found and recorded by reconciliation
+    TreasuryDeficit(Address),     // Shortfall found by the last reconciliation (0 if none)
+    CommittedPayroll(Address),    // Total annual salary of the active employees paid in a token
+    CommittedSalary(Address),     // Annual salary an employee adds to their token's committed payroll
+}
+
+/// Instance keys of deployments that predate per-token treasuries
+#[derive(Clone)]
+#[contracttype]
+pub enum LegacyDataKey {
+    TreasuryBalance,              // Balance of the single payroll token
+}
+
+const DEFAULT_MAX_ARREARS_PERIODS: u32 = 12;
+const DAY_IN_LEDGERS: u32 = 17_280;
+const DEFAULT_TTL_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+const DEFAULT_TTL_EXTEND_TO: u32 = 120 * DAY_IN_LEDGERS;
+const PROPOSAL_LIFETIME: u64 = 7 * calendar::SECONDS_PER_DAY;
+const MAX_SUSTAINABILITY_YEARS: u32 = 1001;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SalaryError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    EmployeeNotFound = 3,
+    EmployeeAlreadyExists = 4,
+    EmployeeNotActive = 5,
+    SalaryAlreadyPaid = 6,
+    InsufficientTreasury = 7,
+    InvalidAmount = 8,
+    InvalidLimit = 9,
+    EmployeeStreaming = 10,
+    NotStreaming = 11,
+    InsufficientAccrued = 12,
+    NoPendingAdmin = 13,
+    Unauthorized = 14,
+    ApprovalRequired = 15,
+    ProposalNotFound = 16,
+    ProposalExpired = 17,
+    ProposalAlreadyExecuted = 18,
+    AlreadyApproved = 19,
+    NotEnoughApprovals = 20,
+    NotSigner = 21,
+    InvalidSigners = 22,
+    ReserveViolation = 23,
+    TokenNotAccepted = 24,
+    InvalidSplit = 25,
+    PayslipNotFound = 26,
+    EmployeeTerminated = 27,
+    InvalidTerminationDate = 28,
+    InvalidEffectiveDate = 29,
+    MigrationPending = 30,
 }
 
 #[contract]
@@ -33,15 +369,16 @@ pub struct SalarySystemContract;
 
 #[contractimpl]
 impl SalarySystemContract {
-    /// Initialize the contract with admin, annual increment rate, and token
+    /// Initialize the contract with admin, annual increment rate, and the
+    /// default payout token (which is also the first accepted token)
     pub fn initialize(
         env: Env,
         admin: Address,
-        annual_increment_percent: u32,
+        annual_increment_bps: u32,
         token_address: Address,
-    ) {
+    ) -> Result<(), SalaryError> {
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("Already initialized");
+            return Err(SalaryError::AlreadyInitialized);
         }
 
         admin.require_auth();
@@ -49,366 +386,2803 @@ impl SalarySystemContract {
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage()
             .instance()
-            .set(&DataKey::AnnualIncrement, &annual_increment_percent);
+            .set(&DataKey::AnnualIncrement, &annual_increment_bps);
         env.storage()
             .instance()
             .set(&DataKey::TokenAddress, &token_address);
-        env.storage().instance().set(&DataKey::TreasuryBalance, &0i128);
         env.storage()
             .instance()
-            .set(&DataKey::EmployeeList, &Vec::<Address>::new(&env));
+            .set(&DataKey::AcceptedTokens, &vec![&env, token_address.clone()]);
+
+        events::Initialized {
+            admin,
+            token: token_address,
+            annual_increment_bps,
+        }
+        .publish(&env);
+        Ok(())
     }
 
-    /// Fund the treasury
-    pub fn fund_treasury(env: Env, from: Address, amount: i128) {
-        from.require_auth();
+    /// Propose a new admin. The transfer completes when `new_admin` calls
+    /// `accept_admin`; a later proposal replaces an earlier one.
+    pub fn propose_admin(env: Env, new_admin: Address) -> Result<(), SalaryError> {
+        let admin = Self::read_admin(&env)?;
+        admin.require_auth();
 
-        let token_address: Address = env
-            .storage()
+        env.storage()
             .instance()
-            .get(&DataKey::TokenAddress)
-            .unwrap();
-        let token_client = token::Client::new(&env, &token_address);
+            .set(&DataKey::PendingAdmin, &new_admin);
 
-        // Transfer tokens to contract
-        token_client.transfer(&from, &env.current_contract_address(), &amount);
+        events::AdminTransferProposed { admin, new_admin }.publish(&env);
+        Ok(())
+    }
 
-        // Update treasury balance
-        let current_balance: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TreasuryBalance)
-            .unwrap_or(0);
-        env.storage()
-            .instance()
-            .set(&DataKey::TreasuryBalance, &(current_balance + amount));
+    /// Accept a pending admin transfer (must be authorized by the proposed admin)
+    pub fn accept_admin(env: Env) -> Result<(), SalaryError> {
+        let previous_admin = Self::read_admin(&env)?;
+        let new_admin = Self::get_pending_admin(env.clone()).ok_or(SalaryError::NoPendingAdmin)?;
+        new_admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        events::AdminTransferAccepted {
+            previous_admin,
+            new_admin,
+        }
+        .publish(&env);
+        Ok(())
     }
 
-    /// Add a new employee
-    pub fn add_employee(
-        env: Env,
-        employee_address: Address,
-        name: String,
-        base_salary: i128,
-    ) {
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+    /// Withdraw a pending admin transfer
+    pub fn cancel_admin_transfer(env: Env) -> Result<(), SalaryError> {
+        let admin = Self::read_admin(&env)?;
         admin.require_auth();
 
-        let (current_year, current_month) = Self::get_current_year_month(&env);
-
-        let employee = Employee {
-            address: employee_address.clone(),
-            name,
-            base_salary,
-            start_year: current_year,
-            start_month: current_month,
-            last_payment_year: current_year,
-            last_payment_month: 0,
-            is_active: true,
-        };
+        let new_admin = Self::get_pending_admin(env.clone()).ok_or(SalaryError::NoPendingAdmin)?;
+        env.storage().instance().remove(&DataKey::PendingAdmin);
 
-        env.storage()
-            .instance()
-            .set(&DataKey::Employee(employee_address.clone()), &employee);
+        events::AdminTransferCancelled { admin, new_admin }.publish(&env);
+        Ok(())
+    }
 
-        // Add to employee list
-        let mut employees: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::EmployeeList)
-            .unwrap_or(Vec::new(&env));
-        employees.push_back(employee_address);
-        env.storage()
-            .instance()
-            .set(&DataKey::EmployeeList, &employees);
+    /// Get the current admin
+    pub fn get_admin(env: Env) -> Result<Address, SalaryError> {
+        Self::read_admin(&env)
     }
 
-    /// Calculate current monthly salary for an employee based on years of service
-    pub fn calculate_current_salary(env: Env, employee_address: Address) -> i128 {
-        let employee: Employee = env
-            .storage()
-            .instance()
-            .get(&DataKey::Employee(employee_address))
-            .unwrap();
+    /// Get the admin awaiting acceptance, if any
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::PendingAdmin)
+    }
 
-        let current_year = Self::get_current_year(&env);
-        let years_of_service = current_year - employee.start_year;
+    /// Grant a role to an account
+    pub fn grant_role(env: Env, role: Role, account: Address) -> Result<(), SalaryError> {
+        let admin = Self::read_admin(&env)?;
+        admin.require_auth();
 
-        let increment_percent: u32 = env
-            .storage()
+        env.storage()
             .instance()
-            .get(&DataKey::AnnualIncrement)
-            .unwrap();
+            .set(&DataKey::Role(role, account.clone()), &true);
 
-        // Calculate annual salary with compound annual increment
-        let mut annual_salary = employee.base_salary;
-        for _ in 0..years_of_service {
-            annual_salary = annual_salary + (annual_salary * increment_percent as i128 / 100);
+        events::RoleGranted {
+            role,
+            account,
+            admin,
         }
-
-        // Convert to monthly salary
-        let monthly_salary = annual_salary / 12;
-        monthly_salary
+        .publish(&env);
+        Ok(())
     }
 
-    /// Pay monthly salary to an employee
-    pub fn pay_salary(env: Env, employee_address: Address) {
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+    /// Revoke a role from an account
+    pub fn revoke_role(env: Env, role: Role, account: Address) -> Result<(), SalaryError> {
+        let admin = Self::read_admin(&env)?;
         admin.require_auth();
 
-        let mut employee: Employee = env
-            .storage()
+        env.storage()
             .instance()
-            .get(&DataKey::Employee(employee_address.clone()))
-            .unwrap();
+            .remove(&DataKey::Role(role, account.clone()));
 
-        // Check if employee is active
-        if !employee.is_active {
-            panic!("Employee is not active");
+        events::RoleRevoked {
+            role,
+            account,
+            admin,
         }
+        .publish(&env);
+        Ok(())
+    }
 
-        let (current_year, current_month) = Self::get_current_year_month(&env);
+    /// Check whether an account has been granted a role
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        env.storage().instance().has(&DataKey::Role(role, account))
+    }
 
-        // Check if already paid this month
-        if employee.last_payment_year == current_year 
-            && employee.last_payment_month == current_month {
-            panic!("Salary already paid for this month");
-        }
+    /// Configure the multisig signer set and how many of them must approve a
+    /// proposal. Once signers are set, this and other guarded changes can only
+    /// be made through `create_proposal`.
+    pub fn set_signers(env: Env, signers: Vec<Address>, threshold: u32) -> Result<(), SalaryError> {
+        Self::read_admin(&env)?.require_auth();
+        Self::require_no_multisig(&env)?;
 
-        let monthly_salary = Self::calculate_current_salary(env.clone(), employee_address.clone());
+        Self::write_signers(&env, &signers, threshold)
+    }
 
-        let treasury_balance: i128 = env
-            .storage()
+    /// Get the multisig signers
+    pub fn get_signers(env: Env) -> Vec<Address> {
+        env.storage()
             .instance()
-            .get(&DataKey::TreasuryBalance)
-            .unwrap();
+            .get(&DataKey::Signers)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the number of approvals required to execute a proposal
+    pub fn get_signer_threshold(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SignerThreshold)
+            .unwrap_or(0)
+    }
+
+    /// Set the annual increment in basis points (requires a proposal under multisig)
+    pub fn set_annual_increment(env: Env, annual_increment_bps: u32) -> Result<(), SalaryError> {
+        Self::read_admin(&env)?.require_auth();
+        Self::require_no_multisig(&env)?;
 
-        if treasury_balance < monthly_salary {
-            panic!("Insufficient treasury balance");
+        Self::apply_action(&env, &ProposalAction::SetAnnualIncrement(annual_increment_bps))
+    }
+
+    /// Propose a guarded change. Only the admin or a signer may propose; a
+    /// signer's proposal counts as their approval.
+    pub fn create_proposal(
+        env: Env,
+        proposer: Address,
+        action: ProposalAction,
+    ) -> Result<u32, SalaryError> {
+        proposer.require_auth();
+
+        let signers = Self::get_signers(env.clone());
+        if signers.is_empty() {
+            return Err(SalaryError::InvalidSigners);
+        }
+        let is_signer = signers.contains(&proposer);
+        if !is_signer && proposer != Self::read_admin(&env)? {
+            return Err(SalaryError::Unauthorized);
         }
 
-        // Transfer salary
-        let token_address: Address = env
+        let id: u32 = env
             .storage()
             .instance()
-            .get(&DataKey::TokenAddress)
-            .unwrap();
-        let token_client = token::Client::new(&env, &token_address);
-
-        token_client.transfer(&env.current_contract_address(), &employee.address, &monthly_salary);
-
-        // Update treasury and employee record
+            .get(&DataKey::NextProposalId)
+            .unwrap_or(1);
         env.storage()
             .instance()
-            .set(&DataKey::TreasuryBalance, &(treasury_balance - monthly_salary));
+            .set(&DataKey::NextProposalId, &(id + 1));
+
+        let mut approvals = Vec::new(&env);
+        if is_signer {
+            approvals.push_back(proposer.clone());
+        }
 
-        employee.last_payment_year = current_year;
-        employee.last_payment_month = current_month;
+        let proposal = Proposal {
+            id,
+            proposer: proposer.clone(),
+            action: action.clone(),
+            approvals,
+            expires_at: env.ledger().timestamp() + PROPOSAL_LIFETIME,
+            executed: false,
+        };
         env.storage()
-            .instance()
-            .set(&DataKey::Employee(employee_address), &employee);
+            .persistent()
+            .set(&DataKey::Proposal(id), &proposal);
+
+        events::ProposalCreated {
+            id,
+            proposer,
+            action,
+        }
+        .publish(&env);
+        Ok(id)
     }
 
-    /// Allow employees to claim their own salary (self-service)
-    pub fn claim_salary(env: Env, employee_address: Address) {
-        // Require authentication from the employee themselves
-        employee_address.require_auth();
+    /// Approve a pending proposal as one of the signers
+    pub fn approve_proposal(env: Env, signer: Address, id: u32) -> Result<(), SalaryError> {
+        signer.require_auth();
 
-        let mut employee: Employee = env
-            .storage()
-            .instance()
-            .get(&DataKey::Employee(employee_address.clone()))
-            .unwrap();
+        if !Self::get_signers(env.clone()).contains(&signer) {
+            return Err(SalaryError::NotSigner);
+        }
 
-        // Check if employee is active
-        if !employee.is_active {
-            panic!("Employee is not active");
+        let mut proposal = Self::get_proposal(env.clone(), id)?;
+        Self::require_open(&env, &proposal)?;
+        if proposal.approvals.contains(&signer) {
+            return Err(SalaryError::AlreadyApproved);
         }
 
-        let (current_year, current_month) = Self::get_current_year_month(&env);
+        proposal.approvals.push_back(signer.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Proposal(id), &proposal);
 
-        // Check if already paid this month
-        if employee.last_payment_year == current_year 
-            && employee.last_payment_month == current_month {
-            panic!("Salary already paid for this month");
+        events::ProposalApproved {
+            id,
+            signer,
+            approvals: proposal.approvals.len(),
         }
+        .publish(&env);
+        Ok(())
+    }
 
-        let monthly_salary = Self::calculate_current_salary(env.clone(), employee_address.clone());
-
-        let treasury_balance: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TreasuryBalance)
-            .unwrap();
+    /// Execute a proposal once enough signers have approved it
+    pub fn execute_proposal(env: Env, id: u32) -> Result<(), SalaryError> {
+        let mut proposal = Self::get_proposal(env.clone(), id)?;
+        Self::require_open(&env, &proposal)?;
 
-        if treasury_balance < monthly_salary {
-            panic!("Insufficient treasury balance");
+        if Self::current_approvals(&env, &proposal) < Self::get_signer_threshold(env.clone()) {
+            return Err(SalaryError::NotEnoughApprovals);
         }
 
-        // Transfer salary
-        let token_address: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::TokenAddress)
-            .unwrap();
-        let token_client = token::Client::new(&env, &token_address);
+        proposal.executed = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Proposal(id), &proposal);
 
-        token_client.transfer(&env.current_contract_address(), &employee.address, &monthly_salary);
+        Self::apply_action(&env, &proposal.action)?;
 
-        // Update treasury and employee record
-        env.storage()
-            .instance()
-            .set(&DataKey::TreasuryBalance, &(treasury_balance - monthly_salary));
+        events::ProposalExecuted { id }.publish(&env);
+        Ok(())
+    }
 
-        employee.last_payment_year = current_year;
-        employee.last_payment_month = current_month;
-        env.storage()
-            .instance()
-            .set(&DataKey::Employee(employee_address), &employee);
+    /// Get a proposal by id
+    pub fn get_proposal(env: Env, id: u32) -> Result<Proposal, SalaryError> {
+        let key = DataKey::Proposal(id);
+        let proposal = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(SalaryError::ProposalNotFound)?;
+        Self::extend_persistent(&env, &key);
+        Ok(proposal)
     }
 
-    /// Stop salary payments for an employee
-    pub fn stop_employee_salary(env: Env, employee_address: Address) {
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
+    /// Get the lifecycle status of a proposal
+    pub fn get_proposal_status(env: Env, id: u32) -> Result<ProposalStatus, SalaryError> {
+        let proposal = Self::get_proposal(env.clone(), id)?;
 
-        let mut employee: Employee = env
-            .storage()
-            .instance()
-            .get(&DataKey::Employee(employee_address.clone()))
-            .unwrap();
+        if proposal.executed {
+            return Ok(ProposalStatus::Executed);
+        }
+        if env.ledger().timestamp() > proposal.expires_at {
+            return Ok(ProposalStatus::Expired);
+        }
 
-        employee.is_active = false;
-        env.storage()
-            .instance()
-            .set(&DataKey::Employee(employee_address), &employee);
+        if Self::current_approvals(&env, &proposal) >= Self::get_signer_threshold(env.clone()) {
+            Ok(ProposalStatus::Approved)
+        } else {
+            Ok(ProposalStatus::Pending)
+        }
     }
 
-    /// Resume salary payments for an employee
-    pub fn resume_employee_salary(env: Env, employee_address: Address) {
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
+    /// Add a token to the payroll token allowlist
+    pub fn add_accepted_token(env: Env, token: Address) -> Result<(), SalaryError> {
+        Self::read_admin(&env)?.require_auth();
 
-        let mut employee: Employee = env
-            .storage()
-            .instance()
-            .get(&DataKey::Employee(employee_address.clone()))
-            .unwrap();
+        let mut tokens = Self::get_accepted_tokens(env.clone());
+        if !tokens.contains(&token) {
+            tokens.push_back(token.clone());
+            env.storage()
+                .instance()
+                .set(&DataKey::AcceptedTokens, &tokens);
 
-        employee.is_active = true;
-        env.storage()
-            .instance()
-            .set(&DataKey::Employee(employee_address), &employee);
+            events::AcceptedTokenAdded { token }.publish(&env);
+        }
+        Ok(())
     }
 
-    /// Calculate how many years the treasury can sustain all employees
-    pub fn calculate_sustainability_years(env: Env) -> u32 {
-        let treasury_balance: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TreasuryBalance)
-            .unwrap_or(0);
+    /// Remove a token from the allowlist. The treasury can no longer be funded
+    /// in it or have employees switched to it; employees already paid in it
+    /// keep drawing on its remaining balance.
+    pub fn remove_accepted_token(env: Env, token: Address) -> Result<(), SalaryError> {
+        Self::read_admin(&env)?.require_auth();
 
-        let employees: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::EmployeeList)
-            .unwrap_or(Vec::new(&env));
+        let mut tokens = Self::get_accepted_tokens(env.clone());
+        if let Some(index) = tokens.first_index_of(&token) {
+            tokens.remove(index);
+            env.storage()
+                .instance()
+                .set(&DataKey::AcceptedTokens, &tokens);
 
-        if employees.is_empty() {
-            return 0;
+            events::AcceptedTokenRemoved { token }.publish(&env);
         }
+        Ok(())
+    }
 
-        let increment_percent: u32 = env
-            .storage()
+    /// Get the tokens the treasury accepts for payroll
+    pub fn get_accepted_tokens(env: Env) -> Vec<Address> {
+        env.storage()
             .instance()
-            .get(&DataKey::AnnualIncrement)
-            .unwrap();
-
-        let current_year = Self::get_current_year(&env);
-        let mut remaining_balance = treasury_balance;
-        let mut years = 0u32;
-
-        // Simulate year by year (calculating annual costs)
-        loop {
-            let mut annual_cost = 0i128;
+            .get(&DataKey::AcceptedTokens)
+            .unwrap_or(Vec::new(&env))
+    }
 
-            // Calculate total annual cost for all employees
-            for i in 0..employees.len() {
-                let employee_addr = employees.get(i).unwrap();
-                let employee: Employee = env
-                    .storage()
-                    .instance()
-                    .get(&DataKey::Employee(employee_addr))
-                    .unwrap();
-
-                let years_of_service = (current_year + years as u64) - employee.start_year;
-                
-                // Calculate annual salary with increments
-                let mut annual_salary = employee.base_salary;
-                for _ in 0..years_of_service {
-                    annual_salary = annual_salary + (annual_salary * increment_percent as i128 / 100);
-                }
+    /// Fund the treasury bucket of an accepted token
+    pub fn fund_treasury(
+        env: Env,
+        from: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), SalaryError> {
+        if amount <= 0 {
+            return Err(SalaryError::InvalidAmount);
+        }
+        Self::require_accepted_token(&env, &token)?;
 
-                annual_cost += annual_salary;
-            }
+        from.require_auth();
 
-            if remaining_balance < annual_cost {
-                break;
-            }
+        let token_client = token::Client::new(&env, &token);
 
-            remaining_balance -= annual_cost;
-            years += 1;
+        // Transfer tokens to contract
+        token_client.transfer(&from, env.current_contract_address(), &amount);
 
-            // Safety limit
-            if years > 1000 {
-                break;
-            }
+        // Update treasury balance
+        let balance = Self::get_treasury_balance(env.clone(), token.clone()) + amount;
+        Self::write_treasury_balance(&env, &token, balance);
+
+        events::TreasuryFunded {
+            token,
+            from,
+            amount,
+            balance,
         }
-
-        years
+        .publish(&env);
+        Ok(())
     }
 
-    /// Get employee details
-    pub fn get_employee(env: Env, employee_address: Address) -> Employee {
-        env.storage()
-            .instance()
-            .get(&DataKey::Employee(employee_address))
-            .unwrap()
+    /// Add a new employee. Under multisig, a base salary above the salary
+    /// approval amount needs a proposal.
+    pub fn add_employee(
+        env: Env,
+        caller: Address,
+        employee_address: Address,
+        name: String,
+        base_salary: i128,
+    ) -> Result<(), SalaryError> {
+        Self::require_role(&env, &caller, Role::Hr)?;
+        Self::require_salary_approval(&env, base_salary)?;
+
+        Self::apply_action(
+            &env,
+            &ProposalAction::AddEmployee(employee_address, name, base_salary),
+        )
     }
 
-    /// Get all employees
-    pub fn get_all_employees(env: Env) -> Vec<Address> {
-        env.storage()
-            .instance()
-            .get(&DataKey::EmployeeList)
-            .unwrap_or(Vec::new(&env))
+    /// Set the annual base salary above which hiring or a salary change needs
+    /// a multisig proposal
+    pub fn set_salary_approval_amount(env: Env, amount: i128) -> Result<(), SalaryError> {
+        Self::read_admin(&env)?.require_auth();
+        Self::require_no_multisig(&env)?;
+
+        Self::apply_action(&env, &ProposalAction::SetSalaryApprovalAmount(amount))
     }
 
-    /// Get treasury balance
-    pub fn get_treasury_balance(env: Env) -> i128 {
+    /// Get the annual base salary above which hiring or a salary change needs
+    /// a multisig proposal
+    pub fn get_salary_approval_amount(env: Env) -> i128 {
         env.storage()
             .instance()
-            .get(&DataKey::TreasuryBalance)
+            .get(&DataKey::SalaryApprovalAmount)
             .unwrap_or(0)
     }
 
-    /// Get annual increment percentage
-    pub fn get_annual_increment(env: Env) -> u32 {
-        env.storage()
+    /// Calculate current per-period salary for an employee based on years of
+    /// service and their pay frequency
+    pub fn calculate_current_salary(
+        env: Env,
+        employee_address: Address,
+    ) -> Result<i128, SalaryError> {
+        let employee = Self::read_employee(&env, &employee_address)?;
+        let policy = Self::employee_increment_policy(&env, &employee)?;
+        let frequency = Self::employee_pay_frequency(&env, &employee);
+
+        Ok(
+            Self::annual_salary_at(&env, &employee, env.ledger().timestamp(), &policy)
+                / frequency.periods_per_year(),
+        )
+    }
+
+    /// Salary owed for every unpaid period up to and including the current one.
+    /// Days before the employee started, and days their salary was stopped,
+    /// are not owed.
+    pub fn get_arrears(env: Env, employee_address: Address) -> Result<Arrears, SalaryError> {
+        let employee = Self::read_employee(&env, &employee_address)?;
+        let policy = Self::employee_increment_policy(&env, &employee)?;
+
+        if employee.streaming {
+            return Ok(Arrears {
+                periods: 0,
+                amount: 0,
+            });
+        }
+
+        let frequency = Self::employee_pay_frequency(&env, &employee);
+        let periods = Self::unpaid_periods(&env, &employee, frequency, u32::MAX);
+        Ok(Arrears {
+            periods: periods.len(),
+            amount: Self::salary_for_periods(
+                &env,
+                &employee,
+                frequency,
+                &periods,
+                &policy,
+            ),
+        })
+    }
+
+    /// Set the maximum number of pay periods a single payment settles
+    pub fn set_max_arrears_periods(env: Env, periods: u32) -> Result<(), SalaryError> {
+        Self::read_admin(&env)?.require_auth();
+
+        if periods == 0 {
+            return Err(SalaryError::InvalidLimit);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxArrearsPeriods, &periods);
+
+        events::MaxArrearsPeriodsChanged { periods }.publish(&env);
+        Ok(())
+    }
+
+    /// Get the maximum number of pay periods a single payment settles
+    pub fn get_max_arrears_periods(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxArrearsPeriods)
+            .unwrap_or(DEFAULT_MAX_ARREARS_PERIODS)
+    }
+
+    /// Replace the deduction rules withheld from every salary payment. Rules
+    /// apply in order, each limited to the pay left after the rules before it.
+    pub fn set_deductions(env: Env, rules: Vec<DeductionRule>) -> Result<(), SalaryError> {
+        Self::read_admin(&env)?.require_auth();
+        Self::require_no_multisig(&env)?;
+
+        Self::apply_action(&env, &ProposalAction::SetDeductions(rules))
+    }
+
+    /// Get the deduction rules withheld from every salary payment
+    pub fn get_deductions(env: Env) -> Vec<DeductionRule> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Deductions)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get a payslip by id
+    pub fn get_payslip(env: Env, id: u64) -> Result<Payslip, SalaryError> {
+        let key = DataKey::Payslip(id);
+        let payslip = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(SalaryError::PayslipNotFound)?;
+        Self::extend_persistent(&env, &key);
+        Ok(payslip)
+    }
+
+    /// Get up to `limit` of an employee's payslips, oldest first, starting at
+    /// their `start`-th payment
+    pub fn get_payslips(
+        env: Env,
+        employee_address: Address,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<Payslip>, SalaryError> {
+        if limit == 0 {
+            return Err(SalaryError::InvalidLimit);
+        }
+
+        let key = DataKey::EmployeePayslips(employee_address);
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        Self::extend_persistent(&env, &key);
+        let end = start.saturating_add(limit).min(ids.len());
+
+        let mut payslips = Vec::new(&env);
+        for i in start..end {
+            payslips.push_back(Self::get_payslip(env.clone(), ids.get(i).unwrap())?);
+        }
+        Ok(payslips)
+    }
+
+    /// Set the pay frequency used by employees without an override
+    pub fn set_default_pay_frequency(
+        env: Env,
+        frequency: PayFrequency,
+    ) -> Result<(), SalaryError> {
+        Self::read_admin(&env)?.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::PayFrequency, &frequency);
+
+        events::DefaultPayFrequencyChanged { frequency }.publish(&env);
+        Ok(())
+    }
+
+    /// Get the pay frequency used by employees without an override
+    pub fn get_default_pay_frequency(env: Env) -> PayFrequency {
+        env.storage()
+            .instance()
+            .get(&DataKey::PayFrequency)
+            .unwrap_or(PayFrequency::Monthly)
+    }
+
+    /// Override (or with `None`, clear the override of) an employee's pay
+    /// frequency. The new frequency applies from the end of the last paid
+    /// period; if that falls inside one of its periods, the rest of that
+    /// period is paid prorated.
+    pub fn set_employee_pay_frequency(
+        env: Env,
+        caller: Address,
+        employee_address: Address,
+        frequency: Option<PayFrequency>,
+    ) -> Result<(), SalaryError> {
+        Self::require_role(&env, &caller, Role::Hr)?;
+
+        let employee = Self::read_employee(&env, &employee_address)?;
+
+        let key = DataKey::EmployeePayFrequency(employee_address.clone());
+        match frequency {
+            Some(frequency) => {
+                env.storage().persistent().set(&key, &frequency);
+                Self::extend_persistent(&env, &key);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
+
+        events::PayFrequencyChanged {
+            employee: employee_address,
+            frequency: Self::employee_pay_frequency(&env, &employee),
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Override (or with `None`, clear the override of) the increment policy
+    /// of an employee, which otherwise gets the global annual increment,
+    /// compounded and uncapped, on every hire anniversary. Under multisig,
+    /// overrides with a higher rate or another basis need a proposal.
+    pub fn set_increment_policy(
+        env: Env,
+        caller: Address,
+        employee_address: Address,
+        policy: Option<IncrementPolicy>,
+    ) -> Result<(), SalaryError> {
+        Self::require_role(&env, &caller, Role::Hr)?;
+        if let Some(policy) = &policy {
+            let default = Self::default_increment_policy(&env)?;
+            if policy.rate > default.rate || policy.basis != default.basis {
+                Self::require_no_multisig(&env)?;
+            }
+        }
+
+        Self::write_increment_policy(&env, &employee_address, policy)
+    }
+
+    /// Get the increment policy in effect for an employee
+    pub fn get_increment_policy(
+        env: Env,
+        employee_address: Address,
+    ) -> Result<IncrementPolicy, SalaryError> {
+        let employee = Self::read_employee(&env, &employee_address)?;
+        Self::employee_increment_policy(&env, &employee)
+    }
+
+    /// Get the pay frequency in effect for an employee
+    pub fn get_pay_frequency(
+        env: Env,
+        employee_address: Address,
+    ) -> Result<PayFrequency, SalaryError> {
+        let employee = Self::read_employee(&env, &employee_address)?;
+        Ok(Self::employee_pay_frequency(&env, &employee))
+    }
+
+    /// Change the token an employee is paid in. Outstanding salary, including
+    /// any streamed balance not yet withdrawn, is paid in the new token.
+    pub fn set_payout_token(
+        env: Env,
+        caller: Address,
+        employee_address: Address,
+        token: Address,
+    ) -> Result<(), SalaryError> {
+        Self::require_role(&env, &caller, Role::Hr)?;
+        Self::require_accepted_token(&env, &token)?;
+
+        let mut employee = Self::read_employee(&env, &employee_address)?;
+        Self::uncommit_salary(&env, &employee);
+        employee.payout_token = token.clone();
+        Self::write_employee(&env, &employee);
+        Self::commit_salary(&env, &employee)?;
+
+        events::PayoutTokenChanged {
+            employee: employee_address,
+            token,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Route shares of every salary payment to other addresses. Shares must be
+    /// positive, go to distinct destinations and add up to 10000 basis points;
+    /// an empty split pays everything to the employee. Rounding dust goes to
+    /// the employee's own address.
+    pub fn set_payout_split(
+        env: Env,
+        employee_address: Address,
+        split: Vec<PayoutShare>,
+    ) -> Result<(), SalaryError> {
+        employee_address.require_auth();
+
+        Self::read_employee(&env, &employee_address)?;
+
+        let key = DataKey::PayoutSplit(employee_address.clone());
+        if split.is_empty() {
+            env.storage().persistent().remove(&key);
+
+            events::PayoutSplitChanged {
+                employee: employee_address,
+                split,
+            }
+            .publish(&env);
+            return Ok(());
+        }
+
+        let mut total = 0u32;
+        for (i, share) in split.iter().enumerate() {
+            if share.basis_points == 0 || share.basis_points > BASIS_POINTS {
+                return Err(SalaryError::InvalidSplit);
+            }
+            let first = split.iter().position(|other| other.destination == share.destination);
+            if first != Some(i) {
+                return Err(SalaryError::InvalidSplit);
+            }
+            total += share.basis_points;
+        }
+        if total != BASIS_POINTS {
+            return Err(SalaryError::InvalidSplit);
+        }
+
+        env.storage().persistent().set(&key, &split);
+        Self::extend_persistent(&env, &key);
+
+        events::PayoutSplitChanged {
+            employee: employee_address,
+            split,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Get an employee's payout split (empty when all salary goes to the employee)
+    pub fn get_payout_split(env: Env, employee_address: Address) -> Vec<PayoutShare> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PayoutSplit(employee_address))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Switch an employee to per-second salary streaming. Accrual starts from the
+    /// end of the last paid period (or the start timestamp, if later), leaving
+    /// out time their salary was stopped; periodic payments are no longer
+    /// available to the employee afterwards.
+    pub fn enable_streaming(
+        env: Env,
+        caller: Address,
+        employee_address: Address,
+    ) -> Result<(), SalaryError> {
+        Self::require_role(&env, &caller, Role::Hr)?;
+
+        let mut employee = Self::read_employee(&env, &employee_address)?;
+        if employee.streaming {
+            return Ok(());
+        }
+
+        employee.streaming = true;
+        employee.accrual_checkpoint = employee.paid_through.max(employee.start_timestamp);
+        if !employee.is_active {
+            employee.accrual_checkpoint = employee.accrual_checkpoint.max(env.ledger().timestamp());
+        }
+        Self::write_employee(&env, &employee);
+
+        events::StreamingEnabled {
+            employee: employee_address,
+            accrual_checkpoint: employee.accrual_checkpoint,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Salary vested to a streaming employee and not yet withdrawn
+    pub fn get_accrued(env: Env, employee_address: Address) -> Result<i128, SalaryError> {
+        let mut employee = Self::read_employee(&env, &employee_address)?;
+        if !employee.streaming {
+            return Err(SalaryError::NotStreaming);
+        }
+
+        Self::settle_accrual(&env, &mut employee)?;
+        Ok(employee.accrued_balance)
+    }
+
+    /// Withdraw up to the vested amount of a streaming employee's salary.
+    /// Percentage deductions are withheld; fixed ones only apply per pay period.
+    pub fn withdraw_accrued(
+        env: Env,
+        employee_address: Address,
+        amount: i128,
+    ) -> Result<Payslip, SalaryError> {
+        employee_address.require_auth();
+
+        if amount <= 0 {
+            return Err(SalaryError::InvalidAmount);
+        }
+
+        let mut employee = Self::read_employee(&env, &employee_address)?;
+        if !employee.streaming {
+            return Err(SalaryError::NotStreaming);
+        }
+
+        Self::settle_accrual(&env, &mut employee)?;
+        if employee.accrued_balance < amount {
+            return Err(SalaryError::InsufficientAccrued);
+        }
+
+        let token = employee.payout_token.clone();
+        let treasury_balance = Self::get_treasury_balance(env.clone(), token.clone());
+        if treasury_balance < amount {
+            return Err(SalaryError::InsufficientTreasury);
+        }
+
+        let now = env.ledger().timestamp();
+        let payslip = Self::disburse(&env, &employee, amount, 0, now, now, &employee_address);
+
+        Self::write_treasury_balance(&env, &token, treasury_balance - amount);
+
+        employee.accrued_balance -= amount;
+        Self::write_employee(&env, &employee);
+        Self::commit_salary(&env, &employee)?;
+        Ok(payslip)
+    }
+
+    /// Pay outstanding salary to an employee, less deductions
+    pub fn pay_salary(
+        env: Env,
+        caller: Address,
+        employee_address: Address,
+    ) -> Result<Payslip, SalaryError> {
+        Self::require_role(&env, &caller, Role::PayrollOperator)?;
+
+        Self::pay_employee(&env, employee_address, &caller)
+    }
+
+    /// Pay every active, not-yet-paid employee at list positions `start..start + limit`.
+    /// Inactive, already-paid and unaffordable employees are skipped and reported.
+    pub fn run_payroll(
+        env: Env,
+        caller: Address,
+        start: u32,
+        limit: u32,
+    ) -> Result<PayrollRun, SalaryError> {
+        Self::require_role(&env, &caller, Role::PayrollOperator)?;
+
+        if limit == 0 {
+            return Err(SalaryError::InvalidLimit);
+        }
+
+        let count = Self::employee_count(&env);
+        let end = start.saturating_add(limit).min(count);
+
+        let mut outcomes = Vec::new(&env);
+        let mut total_paid = 0i128;
+
+        for i in start..end {
+            let employee_address = Self::employee_at(&env, i);
+
+            let (status, amount) = match Self::pay_employee(&env, employee_address.clone(), &caller) {
+                Ok(payslip) => (PayrollStatus::Paid, payslip.gross),
+                Err(SalaryError::EmployeeNotActive) => (PayrollStatus::Inactive, 0),
+                Err(SalaryError::SalaryAlreadyPaid) => (PayrollStatus::AlreadyPaid, 0),
+                Err(SalaryError::InsufficientTreasury) => {
+                    (PayrollStatus::InsufficientTreasury, 0)
+                }
+                Err(SalaryError::EmployeeStreaming) => (PayrollStatus::Streaming, 0),
+                Err(e) => return Err(e),
+            };
+
+            total_paid += amount;
+            outcomes.push_back(PayrollOutcome {
+                employee: employee_address,
+                status,
+                amount,
+            });
+        }
+
+        let next_cursor = if end < count { Some(end) } else { None };
+
+        Ok(PayrollRun {
+            outcomes,
+            total_paid,
+            next_cursor,
+        })
+    }
+
+    /// Allow employees to claim their own salary (self-service)
+    pub fn claim_salary(env: Env, employee_address: Address) -> Result<Payslip, SalaryError> {
+        // Require authentication from the employee themselves
+        employee_address.require_auth();
+
+        Self::pay_employee(&env, employee_address.clone(), &employee_address)
+    }
+
+    /// Stop salary payments for an employee
+    pub fn stop_employee_salary(
+        env: Env,
+        caller: Address,
+        employee_address: Address,
+    ) -> Result<(), SalaryError> {
+        Self::require_role(&env, &caller, Role::Hr)?;
+
+        let mut employee = Self::read_employee(&env, &employee_address)?;
+
+        if employee.is_active {
+            // Streaming accrual stops with the salary
+            Self::settle_accrual(&env, &mut employee)?;
+
+            employee.suspended_at = env.ledger().timestamp();
+        }
+        employee.is_active = false;
+        Self::write_employee(&env, &employee);
+        Self::uncommit_salary(&env, &employee);
+
+        events::SalaryStopped {
+            employee: employee_address,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Resume salary payments for an employee
+    pub fn resume_employee_salary(
+        env: Env,
+        caller: Address,
+        employee_address: Address,
+    ) -> Result<(), SalaryError> {
+        Self::require_role(&env, &caller, Role::Hr)?;
+
+        let mut employee = Self::read_employee(&env, &employee_address)?;
+
+        if !employee.is_active {
+            // Days from stopping up to resuming are not owed
+            employee.inactive_spans.push_back(InactiveSpan {
+                from: employee.suspended_at,
+                to: env.ledger().timestamp(),
+            });
+            employee.accrual_checkpoint = env.ledger().timestamp();
+        }
+        employee.is_active = true;
+        Self::write_employee(&env, &employee);
+        Self::commit_salary(&env, &employee)?;
+
+        events::SalaryResumed {
+            employee: employee_address,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Set how many months of salary are paid as severance on termination
+    /// (requires a proposal under multisig)
+    pub fn set_severance_months(env: Env, months: u32) -> Result<(), SalaryError> {
+        Self::read_admin(&env)?.require_auth();
+        Self::require_no_multisig(&env)?;
+
+        Self::apply_action(&env, &ProposalAction::SetSeveranceMonths(months))
+    }
+
+    /// Get how many months of salary are paid as severance on termination
+    pub fn get_severance_months(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SeveranceMonths)
+            .unwrap_or(0)
+    }
+
+    /// Offboard an employee whose employment ended at `termination_date` (not
+    /// in the future). Pays, less deductions, every unpaid period with the
+    /// last one prorated by the days worked before the termination date (or
+    /// their unwithdrawn salary streamed up to the termination date, less
+    /// anything already settled past it) plus `SeveranceMonths` of salary,
+    /// then archives the record and takes the employee off the list; the last
+    /// employee on the list moves into their position. The employee can no
+    /// longer be paid.
+    pub fn terminate_employee(
+        env: Env,
+        caller: Address,
+        employee_address: Address,
+        termination_date: u64,
+        reason: String,
+    ) -> Result<Termination, SalaryError> {
+        Self::require_role(&env, &caller, Role::Hr)?;
+
+        let mut employee = Self::read_employee(&env, &employee_address)?;
+        if termination_date > env.ledger().timestamp()
+            || termination_date < employee.start_timestamp
+        {
+            return Err(SalaryError::InvalidTerminationDate);
+        }
+
+        let policy = Self::employee_increment_policy(&env, &employee)?;
+        let frequency = Self::employee_pay_frequency(&env, &employee);
+
+        let (owed, periods) = if employee.streaming {
+            if employee.accrual_checkpoint > termination_date {
+                // A stop or withdrawal after the termination date settled
+                // accrual past it, which isn't owed
+                let excess = Self::accrual_between(
+                    &env,
+                    &employee,
+                    termination_date,
+                    employee.accrual_checkpoint,
+                )?;
+                employee.accrued_balance = (employee.accrued_balance - excess).max(0);
+            } else {
+                Self::settle_accrual_until(&env, &mut employee, termination_date)?;
+            }
+            (employee.accrued_balance, 0)
+        } else {
+            Self::final_period_salary(
+                &env,
+                &employee,
+                frequency,
+                termination_date,
+                &policy,
+            )
+        };
+        let severance =
+            Self::annual_salary_at(&env, &employee, termination_date, &policy)
+                * Self::get_severance_months(env.clone()) as i128
+                / 12;
+        let gross = owed + severance;
+
+        let token = employee.payout_token.clone();
+        let treasury_balance = Self::get_treasury_balance(env.clone(), token.clone());
+        if treasury_balance < gross {
+            return Err(SalaryError::InsufficientTreasury);
+        }
+
+        let mut final_payslip = 0;
+        if gross > 0 {
+            let period_start = if employee.streaming {
+                employee.accrual_checkpoint
+            } else if employee.paid_through == 0 {
+                employee.start_timestamp
+            } else {
+                employee.paid_through
+            };
+            final_payslip = Self::disburse(
+                &env,
+                &employee,
+                gross,
+                periods,
+                period_start.min(termination_date),
+                termination_date,
+                &caller,
+            )
+            .id;
+            Self::write_treasury_balance(&env, &token, treasury_balance - gross);
+        }
+
+        employee.is_active = false;
+        employee.accrued_balance = 0;
+        employee.paid_through = employee.paid_through.max(termination_date);
+
+        Self::uncommit_salary(&env, &employee);
+        Self::remove_from_list(&env, &employee_address);
+        for key in [
+            DataKey::Employee(employee_address.clone()),
+            DataKey::EmployeePayFrequency(employee_address.clone()),
+            DataKey::IncrementPolicy(employee_address.clone()),
+            DataKey::PayoutSplit(employee_address.clone()),
+        ] {
+            env.storage().persistent().remove(&key);
+        }
+
+        let termination = Termination {
+            employee,
+            termination_date,
+            reason: reason.clone(),
+            terminated_at: env.ledger().timestamp(),
+            final_payslip,
+        };
+        let key = DataKey::Termination(employee_address.clone());
+        env.storage().persistent().set(&key, &termination);
+        Self::extend_persistent(&env, &key);
+
+        let position = Self::terminated_count(&env);
+        let key = DataKey::TerminatedAt(position);
+        env.storage().persistent().set(&key, &employee_address);
+        Self::extend_persistent(&env, &key);
+        env.storage()
+            .instance()
+            .set(&DataKey::TerminatedCount, &(position + 1));
+
+        events::EmployeeTerminated {
+            employee: employee_address,
+            termination_date,
+            reason,
+            final_payslip,
+            gross,
+        }
+        .publish(&env);
+        Ok(termination)
+    }
+
+    /// Get the archived record of a terminated employee
+    pub fn get_termination(
+        env: Env,
+        employee_address: Address,
+    ) -> Result<Termination, SalaryError> {
+        let key = DataKey::Termination(employee_address);
+        let termination = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(SalaryError::EmployeeNotFound)?;
+        Self::extend_persistent(&env, &key);
+        Ok(termination)
+    }
+
+    /// Calculate how many years the treasury can sustain all employees. Each
+    /// salary is drawn from the bucket of the employee's payout token, so this
+    /// is the number of years until the first bucket runs short. Every
+    /// employee is read in this one call; large rosters should use
+    /// `project_sustainability` instead.
+    pub fn calculate_sustainability_years(env: Env) -> Result<u32, SalaryError> {
+        let mut projection = None;
+        loop {
+            let next = Self::project_sustainability(env.clone(), u32::MAX, projection)?;
+            if next.next_cursor.is_none() {
+                return Ok(next.years);
+            }
+            projection = Some(next);
+        }
+    }
+
+    /// [`Self::calculate_sustainability_years`] in batches of up to `limit`
+    /// employees. Start with `None` and pass each returned projection back
+    /// in until its `next_cursor` is `None`; its `years` is then the result.
+    /// A batch reads each of its employees once and simulates the years one
+    /// by one, stopping at the first year a bucket runs short. Employees in
+    /// later batches only add costs, so no batch looks past the years the
+    /// ones before it left covered.
+    pub fn project_sustainability(
+        env: Env,
+        limit: u32,
+        projection: Option<SustainabilityProjection>,
+    ) -> Result<SustainabilityProjection, SalaryError> {
+        if limit == 0 {
+            return Err(SalaryError::InvalidLimit);
+        }
+
+        let mut projection = projection.unwrap_or(SustainabilityProjection {
+            as_of: env.ledger().timestamp(),
+            tokens: Vec::new(&env),
+            costs: Vec::new(&env),
+            years: MAX_SUSTAINABILITY_YEARS,
+            next_cursor: Some(0),
+        });
+        let start = match projection.next_cursor {
+            Some(start) => start,
+            None => return Ok(projection),
+        };
+
+        let count = Self::employee_count(&env);
+        if count == 0 {
+            projection.years = 0;
+            projection.next_cursor = None;
+            return Ok(projection);
+        }
+
+        let end = start.saturating_add(limit).min(count);
+        let default_policy = Self::default_increment_policy(&env)?;
+        let (current_year, _, _) = calendar::date_from_timestamp(projection.as_of);
+
+        // Salary inputs of the batch's employees, with the index of their payout token
+        let mut inputs = Vec::new(&env);
+        for position in start..end {
+            let employee = Self::peek_employee_at(&env, position)?;
+            let token = match projection.tokens.first_index_of(&employee.payout_token) {
+                Some(token) => token,
+                None => {
+                    projection.tokens.push_back(employee.payout_token.clone());
+                    projection.tokens.len() - 1
+                }
+            };
+            let policy = env
+                .storage()
+                .persistent()
+                .get(&DataKey::IncrementPolicy(employee.address.clone()))
+                .unwrap_or_else(|| default_policy.clone());
+            let history = Self::get_compensation_history(env.clone(), employee.address.clone());
+            inputs.push_back((token, employee.base_salary, employee.start_timestamp, policy, history));
+        }
+
+        let mut balances: Vec<i128> = Vec::new(&env);
+        for token in projection.tokens.iter() {
+            balances.push_back(Self::get_treasury_balance(env.clone(), token));
+        }
+
+        let mut costs = Vec::new(&env);
+        let mut years = 0u32;
+        while years < projection.years {
+            // Salary in force at the start of the year (now, for this year),
+            // added to the costs of the employees in earlier batches
+            let year_start = calendar::timestamp_from_date(current_year + years as u64, 1, 1)
+                .unwrap_or(u64::MAX)
+                .max(projection.as_of);
+            let mut year_costs = projection.costs.get(years).unwrap_or(Vec::new(&env));
+            while year_costs.len() < projection.tokens.len() {
+                year_costs.push_back(0);
+            }
+            for (token, hiring_salary, hired, policy, history) in inputs.iter() {
+                let salary =
+                    Self::annual_salary_on(hiring_salary, hired, &history, year_start, &policy);
+                year_costs.set(token, year_costs.get_unchecked(token) + salary);
+            }
+
+            let mut covered = true;
+            for (token, cost) in year_costs.iter().enumerate() {
+                let balance = balances.get_unchecked(token as u32) - cost;
+                covered &= balance >= 0;
+                balances.set(token as u32, balance);
+            }
+            if !covered {
+                break;
+            }
+            costs.push_back(year_costs);
+            years += 1;
+        }
+
+        projection.costs = costs;
+        projection.years = years;
+        projection.next_cursor = if end < count && years > 0 { Some(end) } else { None };
+        Ok(projection)
+    }
+
+    /// Get employee details
+    pub fn get_employee(env: Env, employee_address: Address) -> Result<Employee, SalaryError> {
+        Self::read_employee(&env, &employee_address)
+    }
+
+    /// Get all employees
+    pub fn get_all_employees(env: Env) -> Vec<Address> {
+        let mut employees = Vec::new(&env);
+        for i in 0..Self::employee_count(&env) {
+            employees.push_back(Self::employee_at(&env, i));
+        }
+        employees
+    }
+
+    /// Employees matching `filter` (all when `None`) among the `limit` list
+    /// positions from `cursor`, so a page may hold fewer than `limit`
+    pub fn list_employees(
+        env: Env,
+        cursor: u32,
+        limit: u32,
+        filter: Option<EmployeeFilter>,
+    ) -> Result<EmployeePage, SalaryError> {
+        if limit == 0 {
+            return Err(SalaryError::InvalidLimit);
+        }
+
+        let count = Self::employee_count(&env);
+        let end = cursor.saturating_add(limit).min(count);
+        let mut employees = Vec::new(&env);
+        for i in cursor..end {
+            let employee = Self::peek_employee_at(&env, i)?;
+            if Self::matches_filter(&employee, &filter) {
+                employees.push_back(employee);
+            }
+        }
+
+        Ok(EmployeePage {
+            employees,
+            next_cursor: if end < count { Some(end) } else { None },
+        })
+    }
+
+    /// Number of employees matching `filter` among the `limit` list positions
+    /// from `cursor`. Without a filter every position from `cursor` matches
+    /// and nothing is read. Add up the batches until the cursor is `None`.
+    pub fn count_employees(
+        env: Env,
+        cursor: u32,
+        limit: u32,
+        filter: Option<EmployeeFilter>,
+    ) -> Result<EmployeeTally, SalaryError> {
+        if limit == 0 {
+            return Err(SalaryError::InvalidLimit);
+        }
+
+        let count = Self::employee_count(&env);
+        if filter.is_none() {
+            return Ok(EmployeeTally {
+                matching: count.saturating_sub(cursor),
+                next_cursor: None,
+            });
+        }
+
+        let end = cursor.saturating_add(limit).min(count);
+        let mut matching = 0u32;
+        for i in cursor..end {
+            if Self::matches_filter(&Self::peek_employee_at(&env, i)?, &filter) {
+                matching += 1;
+            }
+        }
+
+        Ok(EmployeeTally {
+            matching,
+            next_cursor: if end < count { Some(end) } else { None },
+        })
+    }
+
+    /// Assign an employee to a department (empty to clear)
+    pub fn set_employee_department(
+        env: Env,
+        caller: Address,
+        employee_address: Address,
+        department: String,
+    ) -> Result<(), SalaryError> {
+        Self::require_role(&env, &caller, Role::Hr)?;
+
+        let mut employee = Self::read_employee(&env, &employee_address)?;
+        employee.department = department.clone();
+        Self::write_employee(&env, &employee);
+
+        events::DepartmentChanged {
+            employee: employee_address,
+            department,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Change an employee's base annual salary from the first of
+    /// `effective_month`. Past months that have not been paid or accrued yet
+    /// can be changed; future months schedule the change. Increments on the
+    /// new base start with the first January 1st or hire anniversary (per the
+    /// increment policy) after it takes effect. Under multisig,
+    /// a base salary above the salary approval amount needs a proposal.
+    pub fn update_salary(
+        env: Env,
+        caller: Address,
+        employee_address: Address,
+        new_base_salary: i128,
+        effective_year: u64,
+        effective_month: u32,
+        reason: String,
+    ) -> Result<SalaryChange, SalaryError> {
+        Self::require_role(&env, &caller, Role::Hr)?;
+        Self::require_salary_approval(&env, new_base_salary)?;
+
+        Self::record_salary_change(
+            &env,
+            &employee_address,
+            new_base_salary,
+            effective_year,
+            effective_month,
+            reason,
+        )
+    }
+
+    /// Salary changes of an employee ordered by effective date, including
+    /// scheduled ones. The hiring salary is the employee's `base_salary`.
+    pub fn get_compensation_history(env: Env, employee_address: Address) -> Vec<SalaryChange> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CompensationHistory(employee_address))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Set when persistent entries (employees, payslips, terminations,
+    /// proposals) get their TTL extended on access: once it falls below `threshold` ledgers, back up to `extend_to`
+    pub fn set_ttl_config(env: Env, threshold: u32, extend_to: u32) -> Result<(), SalaryError> {
+        Self::read_admin(&env)?.require_auth();
+
+        if threshold > extend_to || extend_to > env.storage().max_ttl() {
+            return Err(SalaryError::InvalidLimit);
+        }
+
+        env.storage().instance().set(
+            &DataKey::TtlConfig,
+            &TtlConfig {
+                threshold,
+                extend_to,
+            },
+        );
+
+        events::TtlConfigChanged {
+            threshold,
+            extend_to,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Get the TTL extension settings for persistent entries
+    pub fn get_ttl_config(env: Env) -> TtlConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::TtlConfig)
+            .unwrap_or(TtlConfig {
+                threshold: DEFAULT_TTL_THRESHOLD,
+                extend_to: DEFAULT_TTL_EXTEND_TO,
+            })
+    }
+
+    /// Extend the TTL of the contract instance and of the persistent entries
+    /// at positions `start..start + limit`. Positions run through the
+    /// employee list, then terminated employees in termination order, then
+    /// proposals by id; an employee's payslips are extended with them.
+    /// Returns the start of the next batch, or `None` when finished.
+    pub fn bump_all(env: Env, start: u32, limit: u32) -> Result<Option<u32>, SalaryError> {
+        Self::read_admin(&env)?.require_auth();
+
+        if limit == 0 {
+            return Err(SalaryError::InvalidLimit);
+        }
+
+        let config = Self::get_ttl_config(env.clone());
+        env.storage()
+            .instance()
+            .extend_ttl(config.threshold, config.extend_to);
+
+        let employees = Self::employee_count(&env);
+        let terminated = Self::terminated_count(&env);
+        let proposals: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextProposalId)
+            .unwrap_or(1)
+            - 1;
+        let count = employees.saturating_add(terminated).saturating_add(proposals);
+        let end = start.saturating_add(limit).min(count);
+        for position in start..end {
+            if position < employees {
+                let employee_address = Self::employee_at(&env, position);
+                for key in [
+                    DataKey::EmployeeAt(position),
+                    DataKey::EmployeeIndex(employee_address.clone()),
+                    DataKey::Employee(employee_address.clone()),
+                    DataKey::EmployeePayFrequency(employee_address.clone()),
+                    DataKey::IncrementPolicy(employee_address.clone()),
+                    DataKey::PayoutSplit(employee_address.clone()),
+                    DataKey::CompensationHistory(employee_address.clone()),
+                    DataKey::CommittedSalary(employee_address.clone()),
+                ] {
+                    Self::extend_persistent(&env, &key);
+                }
+                Self::extend_payslips(&env, &employee_address);
+            } else if position - employees < terminated {
+                let index = DataKey::TerminatedAt(position - employees);
+                let employee_address: Address = env.storage().persistent().get(&index).unwrap();
+                for key in [
+                    index,
+                    DataKey::Termination(employee_address.clone()),
+                    DataKey::CompensationHistory(employee_address.clone()),
+                ] {
+                    Self::extend_persistent(&env, &key);
+                }
+                Self::extend_payslips(&env, &employee_address);
+            } else {
+                let id = position - employees - terminated + 1;
+                Self::extend_persistent(&env, &DataKey::Proposal(id));
+            }
+        }
+
+        Ok(if end < count { Some(end) } else { None })
+    }
+
+    /// Replace the contract's code with uploaded Wasm, keeping its storage
+    /// (requires a proposal under multisig). Deployments upgrading from
+    /// instance-storage employee records then run `migrate_employees`.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), SalaryError> {
+        Self::read_admin(&env)?.require_auth();
+        Self::require_no_multisig(&env)?;
+
+        Self::apply_action(&env, &ProposalAction::Upgrade(new_wasm_hash))
+    }
+
+    /// Convert the records of a deployment upgraded from instance-storage
+    /// employees, for legacy list positions `start..start + limit`. Each
+    /// record moves to persistent storage with its last paid month carried
+    /// over; the first batch also moves the single treasury balance to the
+    /// payout token's bucket and converts the percentage increment to basis
+    /// points, unless `set_annual_increment` already did. Run batches until it
+    /// returns `None`: hiring is refused until then, and the legacy list is
+    /// deleted after the last batch.
+    pub fn migrate_employees(env: Env, start: u32, limit: u32) -> Result<Option<u32>, SalaryError> {
+        Self::read_admin(&env)?.require_auth();
+
+        if limit == 0 {
+            return Err(SalaryError::InvalidLimit);
+        }
+
+        let legacy: Vec<Address> = match env.storage().instance().get(&DataKey::EmployeeList) {
+            Some(legacy) => legacy,
+            None => return Ok(None),
+        };
+        Self::migrate_legacy_config(&env)?;
+        let end = start.saturating_add(limit).min(legacy.len());
+
+        for i in start..end {
+            let employee_address = legacy.get(i).unwrap();
+            let key = DataKey::Employee(employee_address.clone());
+            if let Some(record) = env.storage().instance().get::<_, LegacyEmployee>(&key) {
+                env.storage().instance().remove(&key);
+                let employee = Self::employee_from_legacy(&env, record)?;
+                Self::write_employee(&env, &employee);
+                Self::commit_salary(&env, &employee)?;
+            }
+
+            Self::set_employee_at(&env, i, &employee_address);
+        }
+
+        if end > Self::employee_count(&env) {
+            env.storage().instance().set(&DataKey::EmployeeCount, &end);
+        }
+        if end < legacy.len() {
+            return Ok(Some(end));
+        }
+
+        env.storage().instance().remove(&DataKey::EmployeeList);
+        Ok(None)
+    }
+
+    /// Withdraw funds from a token's treasury bucket. The balance left behind
+    /// must cover that token's reserve; under multisig, amounts above the
+    /// approval amount need a proposal.
+    pub fn withdraw_treasury(
+        env: Env,
+        caller: Address,
+        token: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), SalaryError> {
+        Self::require_role(&env, &caller, Role::Treasurer)?;
+
+        if amount > Self::get_approval_amount(env.clone()) {
+            Self::require_no_multisig(&env)?;
+        }
+
+        Self::withdraw(&env, &token, &to, amount)
+    }
+
+    /// Set the withdrawal amount above which a multisig proposal is required
+    pub fn set_approval_amount(env: Env, amount: i128) -> Result<(), SalaryError> {
+        Self::read_admin(&env)?.require_auth();
+        Self::require_no_multisig(&env)?;
+
+        Self::apply_action(&env, &ProposalAction::SetApprovalAmount(amount))
+    }
+
+    /// Get the withdrawal amount above which a multisig proposal is required
+    pub fn get_approval_amount(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ApprovalAmount)
+            .unwrap_or(0)
+    }
+
+    /// Set how many months of committed payroll withdrawals must leave in the treasury
+    pub fn set_reserve_months(env: Env, months: u32) -> Result<(), SalaryError> {
+        Self::read_admin(&env)?.require_auth();
+        Self::require_no_multisig(&env)?;
+
+        Self::apply_action(&env, &ProposalAction::SetReserveMonths(months))
+    }
+
+    /// Get how many months of committed payroll withdrawals must leave in the treasury
+    pub fn get_reserve_months(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ReserveMonths)
+            .unwrap_or(0)
+    }
+
+    /// Balance withdrawals must leave in a token's bucket: `ReserveMonths` of
+    /// its committed payroll
+    pub fn get_required_reserve(env: Env, token: Address) -> i128 {
+        Self::get_committed_payroll(env.clone(), token)
+            * Self::get_reserve_months(env) as i128
+            / 12
+    }
+
+    /// Total annual salary of the active employees paid in a token, each
+    /// counted at the salary in force when they were last hired, paid,
+    /// stopped, resumed, moved to the token or given a salary or increment
+    /// policy change
+    pub fn get_committed_payroll(env: Env, token: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::CommittedPayroll(token))
+            .unwrap_or(0)
+    }
+
+    /// Compare an accepted token's tracked treasury balance with the
+    /// contract's actual balance of it. A surplus (direct transfers to the contract) is added to
+    /// the treasury and recorded as untracked deposits; a shortfall (e.g. token
+    /// fees) is flagged as a deficit and left for the admin to resolve. Returns
+    /// the actual minus tracked balance found.
+    pub fn reconcile_treasury(
+        env: Env,
+        caller: Address,
+        token: Address,
+    ) -> Result<i128, SalaryError> {
+        Self::require_role(&env, &caller, Role::Auditor)?;
+        Self::require_accepted_token(&env, &token)?;
+
+        let tracked = Self::get_treasury_balance(env.clone(), token.clone());
+        let actual = token::Client::new(&env, &token).balance(&env.current_contract_address());
+        let discrepancy = actual - tracked;
+
+        let mut surplus = 0i128;
+        let mut deficit = 0i128;
+        if discrepancy > 0 {
+            surplus = discrepancy;
+            Self::write_treasury_balance(&env, &token, actual);
+            env.storage().instance().set(
+                &DataKey::UntrackedDeposits(token.clone()),
+                &(Self::get_untracked_deposits(env.clone(), token.clone()) + surplus),
+            );
+        } else {
+            deficit = -discrepancy;
+        }
+        if deficit > 0 {
+            env.storage()
+                .instance()
+                .set(&DataKey::TreasuryDeficit(token.clone()), &deficit);
+        } else {
+            env.storage()
+                .instance()
+                .remove(&DataKey::TreasuryDeficit(token.clone()));
+        }
+
+        events::TreasuryReconciled {
+            token,
+            tracked,
+            actual,
+            surplus,
+            deficit,
+        }
+        .publish(&env);
+        Ok(discrepancy)
+    }
+
+    /// Actual token balance minus the tracked treasury balance (positive for
+    /// unrecorded deposits, negative for a shortfall)
+    pub fn get_treasury_discrepancy(env: Env, token: Address) -> i128 {
+        let actual = token::Client::new(&env, &token).balance(&env.current_contract_address());
+        actual - Self::get_treasury_balance(env, token)
+    }
+
+    /// Total surplus of a token recorded by reconciliation
+    pub fn get_untracked_deposits(env: Env, token: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::UntrackedDeposits(token))
+            .unwrap_or(0)
+    }
+
+    /// Shortfall of a token found by its last reconciliation (0 if none)
+    pub fn get_treasury_deficit(env: Env, token: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TreasuryDeficit(token))
+            .unwrap_or(0)
+    }
+
+    /// Get the treasury balance of a token
+    pub fn get_treasury_balance(env: Env, token: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Treasury(token))
+            .unwrap_or(0)
+    }
+
+    /// Get annual increment in basis points
+    pub fn get_annual_increment(env: Env) -> Result<u32, SalaryError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::AnnualIncrement)
+            .ok_or(SalaryError::NotInitialized)
+    }
+
+    /// Pay all outstanding periods (oldest first, up to `MaxArrearsPeriods`) to
+    /// an active employee from the treasury
+    fn pay_employee(
+        env: &Env,
+        employee_address: Address,
+        paid_by: &Address,
+    ) -> Result<Payslip, SalaryError> {
+        let mut employee = Self::read_employee(env, &employee_address)?;
+
+        // Check if employee is active
+        if !employee.is_active {
+            return Err(SalaryError::EmployeeNotActive);
+        }
+        if employee.streaming {
+            return Err(SalaryError::EmployeeStreaming);
+        }
+
+        let frequency = Self::employee_pay_frequency(env, &employee);
+        let periods = Self::unpaid_periods(
+            env,
+            &employee,
+            frequency,
+            Self::get_max_arrears_periods(env.clone()),
+        );
+
+        // Check if already paid through this period
+        let last_period = match periods.last() {
+            Some(period) => period,
+            None => return Err(SalaryError::SalaryAlreadyPaid),
+        };
+
+        let policy = Self::employee_increment_policy(env, &employee)?;
+        let amount = Self::salary_for_periods(
+            env,
+            &employee,
+            frequency,
+            &periods,
+            &policy,
+        );
+
+        let token = employee.payout_token.clone();
+        let treasury_balance = Self::get_treasury_balance(env.clone(), token.clone());
+
+        if treasury_balance < amount {
+            return Err(SalaryError::InsufficientTreasury);
+        }
+
+        // Transfer salary
+        let payslip = Self::disburse(
+            env,
+            &employee,
+            amount,
+            periods.len(),
+            frequency
+                .period_start(periods.first().unwrap())
+                .max(employee.paid_through),
+            frequency.period_start(last_period + 1),
+            paid_by,
+        );
+
+        // Update treasury and employee record
+        Self::write_treasury_balance(env, &token, treasury_balance - amount);
+
+        let (paid_year, paid_month, _) =
+            calendar::date_from_timestamp(frequency.period_start(last_period));
+        employee.last_payment_year = paid_year;
+        employee.last_payment_month = paid_month;
+        employee.paid_through = frequency.period_start(last_period + 1);
+
+        let mut inactive_spans = Vec::new(env);
+        for span in employee.inactive_spans.iter() {
+            if span.to > employee.paid_through {
+                inactive_spans.push_back(span);
+            }
+        }
+        employee.inactive_spans = inactive_spans;
+
+        Self::write_employee(env, &employee);
+        Self::commit_salary(env, &employee)?;
+        Ok(payslip)
+    }
+
+    /// Withhold deductions from `gross` (fixed ones once per pay period), send
+    /// each to its destination, pay the rest to the employee and record the payslip
+    fn disburse(
+        env: &Env,
+        employee: &Employee,
+        gross: i128,
+        periods: u32,
+        period_start: u64,
+        period_end: u64,
+        paid_by: &Address,
+    ) -> Payslip {
+        let token_client = token::Client::new(env, &employee.payout_token);
+
+        let mut deductions = Vec::new(env);
+        let mut net = gross;
+        for rule in Self::get_deductions(env.clone()).iter() {
+            let amount = match rule.kind {
+                DeductionKind::Percentage(basis_points) => {
+                    fixed::apply_bps(gross, basis_points)
+                }
+                DeductionKind::Fixed(amount) => amount * periods as i128,
+            }
+            .min(net);
+
+            if amount > 0 {
+                token_client.transfer(&env.current_contract_address(), &rule.destination, &amount);
+                net -= amount;
+            }
+            deductions.push_back(DeductionLine {
+                name: rule.name,
+                destination: rule.destination,
+                amount,
+            });
+        }
+
+        Self::transfer_salary(env, employee, net);
+
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextPayslipId)
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextPayslipId, &(id + 1));
+
+        let payslip = Payslip {
+            id,
+            employee: employee.address.clone(),
+            periods,
+            period_start,
+            period_end,
+            gross,
+            deductions,
+            net,
+            token: employee.payout_token.clone(),
+            paid_at: env.ledger().timestamp(),
+            paid_by: paid_by.clone(),
+            self_claim: *paid_by == employee.address,
+        };
+        let payslip_key = DataKey::Payslip(id);
+        env.storage().persistent().set(&payslip_key, &payslip);
+        Self::extend_persistent(env, &payslip_key);
+
+        let key = DataKey::EmployeePayslips(employee.address.clone());
+        let mut ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        ids.push_back(id);
+        env.storage().persistent().set(&key, &ids);
+        Self::extend_persistent(env, &key);
+
+        events::SalaryPaid {
+            employee: employee.address.clone(),
+            payslip_id: id,
+            token: payslip.token.clone(),
+            periods,
+            gross,
+            net,
+            paid_by: paid_by.clone(),
+            self_claim: payslip.self_claim,
+        }
+        .publish(env);
+        payslip
+    }
+
+    /// Transfer a salary payment in the employee's payout token, split across
+    /// their payout destinations with any rounding dust going to the employee
+    fn transfer_salary(env: &Env, employee: &Employee, amount: i128) {
+        let token_client = token::Client::new(env, &employee.payout_token);
+        let contract = env.current_contract_address();
+
+        let mut remaining = amount;
+        for share in Self::get_payout_split(env.clone(), employee.address.clone()).iter() {
+            if share.destination == employee.address {
+                continue;
+            }
+            let part = fixed::apply_bps(amount, share.basis_points);
+            if part > 0 {
+                token_client.transfer(&contract, &share.destination, &part);
+                remaining -= part;
+            }
+        }
+
+        if remaining > 0 {
+            token_client.transfer(&contract, &employee.address, &remaining);
+        }
+    }
+
+    /// Unpaid period indices owed to an employee, oldest first and at most `max`
+    fn unpaid_periods(
+        env: &Env,
+        employee: &Employee,
+        frequency: PayFrequency,
+        max: u32,
+    ) -> Vec<u64> {
+        let until = if employee.is_active {
+            env.ledger().timestamp()
+        } else {
+            employee.suspended_at
+        };
+        Self::unpaid_periods_until(env, employee, frequency, max, until)
+    }
+
+    /// Unpaid period indices up to and including the one containing `until`,
+    /// starting with the period `paid_through` falls in (which is only partly
+    /// unpaid if the pay frequency changed since)
+    fn unpaid_periods_until(
+        env: &Env,
+        employee: &Employee,
+        frequency: PayFrequency,
+        max: u32,
+        until: u64,
+    ) -> Vec<u64> {
+        let through = frequency.period_index(until);
+
+        let mut period = if employee.paid_through == 0 {
+            frequency.period_index(employee.start_timestamp)
+        } else {
+            frequency.period_index(employee.paid_through)
+        };
+
+        let mut periods = Vec::new(env);
+        while period <= through && periods.len() < max {
+            let start_day = frequency.period_start(period) / calendar::SECONDS_PER_DAY;
+            let end_day = frequency.period_start(period + 1) / calendar::SECONDS_PER_DAY;
+            if Self::inactive_days(employee, start_day, end_day) < end_day - start_day {
+                periods.push_back(period);
+            }
+            period += 1;
+        }
+        periods
+    }
+
+    /// Total salary for the given periods, at the salary in force on each day
+    fn salary_for_periods(
+        env: &Env,
+        employee: &Employee,
+        frequency: PayFrequency,
+        periods: &Vec<u64>,
+        policy: &IncrementPolicy,
+    ) -> i128 {
+        let history = Self::get_compensation_history(env.clone(), employee.address.clone());
+        let mut total = 0i128;
+        for period in periods.iter() {
+            total +=
+                Self::period_salary(employee, &history, frequency, period, u64::MAX, policy);
+        }
+        total
+    }
+
+    /// Salary for one period. A period a salary change or increment takes
+    /// effect in is split there, each part paid at the salary then in force
+    /// for its share of the period's days. The hire period is prorated by the
+    /// days worked from the hire date (inclusive), a period partly paid under
+    /// an earlier pay frequency by the days after `paid_through`, a period cut
+    /// short by `until` by the days worked before it, and a period the
+    /// employee was partly stopped in by the days they weren't.
+    fn period_salary(
+        employee: &Employee,
+        history: &Vec<SalaryChange>,
+        frequency: PayFrequency,
+        period: u64,
+        until: u64,
+        policy: &IncrementPolicy,
+    ) -> i128 {
+        let start = frequency.period_start(period);
+        let end = frequency.period_start(period + 1);
+
+        let period_days = (end - start) / calendar::SECONDS_PER_DAY;
+        let first_day = start.max(employee.start_timestamp).max(employee.paid_through)
+            / calendar::SECONDS_PER_DAY;
+        let last_day = end.min(until) / calendar::SECONDS_PER_DAY;
+
+        let mut amount = 0i128;
+        let mut from = start;
+        while from < end {
+            let next_change = history
+                .iter()
+                .map(|change| change.effective_from)
+                .find(|&effective_from| effective_from > from)
+                .unwrap_or(u64::MAX);
+            let (_, since) = Self::base_salary_from(employee, history, from);
+            let next_increment =
+                policy.next_increment_after(employee.start_timestamp, since, from);
+            let to = end.min(next_change).min(next_increment);
+
+            let from_day = (from / calendar::SECONDS_PER_DAY).max(first_day);
+            let to_day = (to / calendar::SECONDS_PER_DAY).min(last_day);
+            if to_day > from_day {
+                let days_worked =
+                    to_day - from_day - Self::inactive_days(employee, from_day, to_day);
+                let salary = Self::annual_salary_from(employee, history, from, policy)
+                    / frequency.periods_per_year();
+                amount += salary * days_worked as i128 / period_days as i128;
+            }
+            from = to;
+        }
+        amount
+    }
+
+    /// Days in `[first_day, last_day)` (days since the epoch) the employee was
+    /// stopped, from the day salary stopped (inclusive) to the day it resumed
+    fn inactive_days(employee: &Employee, first_day: u64, last_day: u64) -> u64 {
+        let mut days = 0;
+        for span in employee.inactive_spans.iter() {
+            let from = (span.from / calendar::SECONDS_PER_DAY).max(first_day);
+            let to = (span.to / calendar::SECONDS_PER_DAY).min(last_day);
+            days += to.saturating_sub(from);
+        }
+        if !employee.is_active {
+            let from = (employee.suspended_at / calendar::SECONDS_PER_DAY).max(first_day);
+            days += last_day.saturating_sub(from);
+        }
+        days
+    }
+
+    /// Salary owed up to `termination_date`: every unpaid period, with the
+    /// period it falls in prorated by the days worked before it. Returns the
+    /// amount and the number of periods it covers.
+    fn final_period_salary(
+        env: &Env,
+        employee: &Employee,
+        frequency: PayFrequency,
+        termination_date: u64,
+        policy: &IncrementPolicy,
+    ) -> (i128, u32) {
+        let until = if employee.is_active {
+            termination_date
+        } else {
+            termination_date.min(employee.suspended_at)
+        };
+
+        let history = Self::get_compensation_history(env.clone(), employee.address.clone());
+        let mut amount = 0i128;
+        let mut count = 0u32;
+        for period in Self::unpaid_periods_until(env, employee, frequency, u32::MAX, until).iter() {
+            if frequency.period_start(period) < until {
+                amount +=
+                    Self::period_salary(employee, &history, frequency, period, until, policy);
+                count += 1;
+            }
+        }
+        (amount, count)
+    }
+
+    fn employee_pay_frequency(env: &Env, employee: &Employee) -> PayFrequency {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EmployeePayFrequency(employee.address.clone()))
+            .unwrap_or_else(|| Self::get_default_pay_frequency(env.clone()))
+    }
+
+    /// Move salary vested since the accrual checkpoint into the accrued balance.
+    /// Each calendar year accrues its annual salary evenly over its seconds,
+    /// rounded down, switching rate when a salary change or increment takes
+    /// effect.
+    fn settle_accrual(env: &Env, employee: &mut Employee) -> Result<(), SalaryError> {
+        Self::settle_accrual_until(env, employee, env.ledger().timestamp())
+    }
+
+    fn settle_accrual_until(
+        env: &Env,
+        employee: &mut Employee,
+        now: u64,
+    ) -> Result<(), SalaryError> {
+        if !employee.streaming || !employee.is_active || employee.accrual_checkpoint >= now {
+            return Ok(());
+        }
+
+        employee.accrued_balance +=
+            Self::accrual_between(env, employee, employee.accrual_checkpoint, now)?;
+        employee.accrual_checkpoint = now;
+        Ok(())
+    }
+
+    /// Salary streamed over `[since, until)`, leaving out spans the employee
+    /// was inactive
+    fn accrual_between(
+        env: &Env,
+        employee: &Employee,
+        since: u64,
+        until: u64,
+    ) -> Result<i128, SalaryError> {
+        let policy = Self::employee_increment_policy(env, employee)?;
+        let history = Self::get_compensation_history(env.clone(), employee.address.clone());
+
+        let mut accrued = 0i128;
+        let mut from = since;
+        for span in employee.inactive_spans.iter() {
+            if span.from >= until {
+                break;
+            }
+            if span.from > from {
+                accrued += Self::streamed_between(employee, &history, &policy, from, span.from);
+            }
+            from = from.max(span.to);
+        }
+        if from < until {
+            accrued += Self::streamed_between(employee, &history, &policy, from, until);
+        }
+        Ok(accrued)
+    }
+
+    /// Salary streamed over `[since, until)` while active throughout, split
+    /// wherever the annual salary changes
+    fn streamed_between(
+        employee: &Employee,
+        history: &Vec<SalaryChange>,
+        policy: &IncrementPolicy,
+        since: u64,
+        until: u64,
+    ) -> i128 {
+        let mut from = since;
+        let mut accrued = 0i128;
+        while from < until {
+            let (year, _, _) = calendar::date_from_timestamp(from);
+            let year_start = calendar::timestamp_from_date(year, 1, 1).unwrap_or(from);
+            let year_end = calendar::timestamp_from_date(year + 1, 1, 1).unwrap_or(u64::MAX);
+            let next_change = history
+                .iter()
+                .map(|change| change.effective_from)
+                .find(|&effective_from| effective_from > from)
+                .unwrap_or(u64::MAX);
+            let (_, since) = Self::base_salary_from(employee, history, from);
+            let next_increment =
+                policy.next_increment_after(employee.start_timestamp, since, from);
+            let to = until.min(year_end).min(next_change).min(next_increment);
+
+            let annual_salary = Self::annual_salary_from(employee, history, from, policy);
+            accrued += annual_salary * (to - from) as i128 / (year_end - year_start) as i128;
+            from = to;
+        }
+        accrued
+    }
+
+    /// Annual salary in force at `timestamp`: the latest salary change
+    /// effective by then (or the hiring salary), with the increments the
+    /// policy grants since it took effect
+    fn annual_salary_at(
+        env: &Env,
+        employee: &Employee,
+        timestamp: u64,
+        policy: &IncrementPolicy,
+    ) -> i128 {
+        let history = Self::get_compensation_history(env.clone(), employee.address.clone());
+        Self::annual_salary_from(employee, &history, timestamp, policy)
+    }
+
+    /// [`Self::annual_salary_at`] for an already loaded compensation history
+    fn annual_salary_from(
+        employee: &Employee,
+        history: &Vec<SalaryChange>,
+        timestamp: u64,
+        policy: &IncrementPolicy,
+    ) -> i128 {
+        Self::annual_salary_on(
+            employee.base_salary,
+            employee.start_timestamp,
+            history,
+            timestamp,
+            policy,
+        )
+    }
+
+    /// [`Self::annual_salary_from`] given only the hiring salary and date
+    fn annual_salary_on(
+        hiring_salary: i128,
+        hired: u64,
+        history: &Vec<SalaryChange>,
+        timestamp: u64,
+        policy: &IncrementPolicy,
+    ) -> i128 {
+        let mut base = (hiring_salary, hired);
+        for change in history.iter() {
+            if change.effective_from > timestamp {
+                break;
+            }
+            base = (change.base_salary, change.effective_from);
+        }
+        let (base_salary, since) = base;
+        policy.apply(base_salary, policy.increments_between(hired, since, timestamp))
+    }
+
+    /// Base salary in force at `timestamp` and when it took effect
+    fn base_salary_from(
+        employee: &Employee,
+        history: &Vec<SalaryChange>,
+        timestamp: u64,
+    ) -> (i128, u64) {
+        let mut base = (employee.base_salary, employee.start_timestamp);
+        for change in history.iter() {
+            if change.effective_from > timestamp {
+                break;
+            }
+            base = (change.base_salary, change.effective_from);
+        }
+        base
+    }
+
+    /// Store or clear an employee's increment policy override
+    fn write_increment_policy(
+        env: &Env,
+        employee_address: &Address,
+        policy: Option<IncrementPolicy>,
+    ) -> Result<(), SalaryError> {
+        let employee = Self::read_employee(env, employee_address)?;
+
+        let key = DataKey::IncrementPolicy(employee_address.clone());
+        match policy {
+            Some(policy) => {
+                if policy.cap < 0 {
+                    return Err(SalaryError::InvalidAmount);
+                }
+                env.storage().persistent().set(&key, &policy);
+                Self::extend_persistent(env, &key);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
+        Self::commit_salary(env, &employee)?;
+
+        events::IncrementPolicyChanged {
+            employee: employee_address.clone(),
+            policy: Self::employee_increment_policy(env, &employee)?,
+        }
+        .publish(env);
+        Ok(())
+    }
+
+    fn employee_increment_policy(
+        env: &Env,
+        employee: &Employee,
+    ) -> Result<IncrementPolicy, SalaryError> {
+        match env
+            .storage()
+            .persistent()
+            .get(&DataKey::IncrementPolicy(employee.address.clone()))
+        {
+            Some(policy) => Ok(policy),
+            None => Self::default_increment_policy(env),
+        }
+    }
+
+    /// Global annual increment, compounded and uncapped on hire anniversaries
+    fn default_increment_policy(env: &Env) -> Result<IncrementPolicy, SalaryError> {
+        Ok(IncrementPolicy {
+            rate: Self::get_annual_increment(env.clone())?,
+            method: IncrementMethod::Compound,
+            cap: 0,
+            basis: IncrementBasis::Anniversary,
+        })
+    }
+
+    /// Guarded changes must go through a proposal once signers are configured
+    fn require_no_multisig(env: &Env) -> Result<(), SalaryError> {
+        if Self::get_signers(env.clone()).is_empty() {
+            Ok(())
+        } else {
+            Err(SalaryError::ApprovalRequired)
+        }
+    }
+
+    /// Base salaries above the salary approval amount need a proposal under multisig
+    fn require_salary_approval(env: &Env, base_salary: i128) -> Result<(), SalaryError> {
+        if base_salary > Self::get_salary_approval_amount(env.clone()) {
+            Self::require_no_multisig(env)?;
+        }
+        Ok(())
+    }
+
+    fn require_open(env: &Env, proposal: &Proposal) -> Result<(), SalaryError> {
+        if proposal.executed {
+            return Err(SalaryError::ProposalAlreadyExecuted);
+        }
+        if env.ledger().timestamp() > proposal.expires_at {
+            return Err(SalaryError::ProposalExpired);
+        }
+        Ok(())
+    }
+
+    /// Approvals from signers that are still in the signer set
+    fn current_approvals(env: &Env, proposal: &Proposal) -> u32 {
+        let signers = Self::get_signers(env.clone());
+        proposal
+            .approvals
+            .iter()
+            .filter(|signer| signers.contains(signer))
+            .count() as u32
+    }
+
+    fn apply_action(env: &Env, action: &ProposalAction) -> Result<(), SalaryError> {
+        match action {
+            ProposalAction::SetAnnualIncrement(basis_points) => {
+                // Convert a legacy configuration first, so the new rate isn't
+                // taken for a percentage later
+                if env.storage().instance().has(&DataKey::EmployeeList) {
+                    Self::migrate_legacy_config(env)?;
+                }
+                env.storage()
+                    .instance()
+                    .set(&DataKey::AnnualIncrement, basis_points);
+
+                events::AnnualIncrementChanged {
+                    annual_increment_bps: *basis_points,
+                }
+                .publish(env);
+            }
+            ProposalAction::SetSigners(signers, threshold) => {
+                Self::write_signers(env, signers, *threshold)?;
+            }
+            ProposalAction::SetApprovalAmount(amount) => {
+                if *amount < 0 {
+                    return Err(SalaryError::InvalidAmount);
+                }
+                env.storage()
+                    .instance()
+                    .set(&DataKey::ApprovalAmount, amount);
+
+                events::ApprovalAmountChanged { amount: *amount }.publish(env);
+            }
+            ProposalAction::SetReserveMonths(months) => {
+                env.storage()
+                    .instance()
+                    .set(&DataKey::ReserveMonths, months);
+
+                events::ReserveMonthsChanged { months: *months }.publish(env);
+            }
+            ProposalAction::WithdrawTreasury(token, to, amount) => {
+                Self::withdraw(env, token, to, *amount)?;
+            }
+            ProposalAction::SetSalaryApprovalAmount(amount) => {
+                if *amount < 0 {
+                    return Err(SalaryError::InvalidAmount);
+                }
+                env.storage()
+                    .instance()
+                    .set(&DataKey::SalaryApprovalAmount, amount);
+
+                events::SalaryApprovalAmountChanged { amount: *amount }.publish(env);
+            }
+            ProposalAction::AddEmployee(employee_address, name, base_salary) => {
+                Self::insert_employee(env, employee_address, name, *base_salary)?;
+            }
+            ProposalAction::SetSeveranceMonths(months) => {
+                env.storage()
+                    .instance()
+                    .set(&DataKey::SeveranceMonths, months);
+
+                events::SeveranceMonthsChanged { months: *months }.publish(env);
+            }
+            ProposalAction::UpdateSalary(
+                employee_address,
+                new_base_salary,
+                effective_year,
+                effective_month,
+                reason,
+            ) => {
+                Self::record_salary_change(
+                    env,
+                    employee_address,
+                    *new_base_salary,
+                    *effective_year,
+                    *effective_month,
+                    reason.clone(),
+                )?;
+            }
+            ProposalAction::SetIncrementPolicy(employee_address, policy) => {
+                Self::write_increment_policy(env, employee_address, Some(policy.clone()))?;
+            }
+            ProposalAction::Upgrade(new_wasm_hash) => {
+                env.deployer()
+                    .update_current_contract_wasm(new_wasm_hash.clone());
+            }
+            ProposalAction::SetDeductions(rules) => {
+                for rule in rules.iter() {
+                    let valid = match rule.kind {
+                        DeductionKind::Percentage(basis_points) => {
+                            basis_points > 0 && basis_points <= BASIS_POINTS
+                        }
+                        DeductionKind::Fixed(amount) => amount > 0,
+                    };
+                    if !valid {
+                        return Err(SalaryError::InvalidAmount);
+                    }
+                }
+                env.storage().instance().set(&DataKey::Deductions, rules);
+
+                events::DeductionsChanged {
+                    rules: rules.clone(),
+                }
+                .publish(env);
+            }
+        }
+        Ok(())
+    }
+
+    fn withdraw(env: &Env, token: &Address, to: &Address, amount: i128) -> Result<(), SalaryError> {
+        if amount <= 0 {
+            return Err(SalaryError::InvalidAmount);
+        }
+
+        let treasury_balance = Self::get_treasury_balance(env.clone(), token.clone());
+        if treasury_balance < amount {
+            return Err(SalaryError::InsufficientTreasury);
+        }
+        let reserve = Self::get_required_reserve(env.clone(), token.clone());
+        if treasury_balance - amount < reserve {
+            return Err(SalaryError::ReserveViolation);
+        }
+
+        let token_client = token::Client::new(env, token);
+        token_client.transfer(&env.current_contract_address(), to, &amount);
+
+        let balance = treasury_balance - amount;
+        Self::write_treasury_balance(env, token, balance);
+
+        events::TreasuryWithdrawn {
+            token: token.clone(),
+            to: to.clone(),
+            amount,
+            balance,
+            reserve,
+        }
+        .publish(env);
+        Ok(())
+    }
+
+    fn write_signers(env: &Env, signers: &Vec<Address>, threshold: u32) -> Result<(), SalaryError> {
+        if threshold == 0 || threshold > signers.len() {
+            return Err(SalaryError::InvalidSigners);
+        }
+        for (i, signer) in signers.iter().enumerate() {
+            if signers.first_index_of(&signer) != Some(i as u32) {
+                return Err(SalaryError::InvalidSigners);
+            }
+        }
+
+        env.storage().instance().set(&DataKey::Signers, signers);
+        env.storage()
+            .instance()
+            .set(&DataKey::SignerThreshold, &threshold);
+
+        events::SignersChanged {
+            signers: signers.clone(),
+            threshold,
+        }
+        .publish(env);
+        Ok(())
+    }
+
+    /// Require `caller` to authorize and to be the admin or hold `role`
+    fn require_role(env: &Env, caller: &Address, role: Role) -> Result<(), SalaryError> {
+        caller.require_auth();
+
+        if *caller == Self::read_admin(env)?
+            || Self::has_role(env.clone(), role, caller.clone())
+        {
+            Ok(())
+        } else {
+            Err(SalaryError::Unauthorized)
+        }
+    }
+
+    fn read_admin(env: &Env) -> Result<Address, SalaryError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(SalaryError::NotInitialized)
+    }
+
+    fn read_token_address(env: &Env) -> Result<Address, SalaryError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::TokenAddress)
+            .ok_or(SalaryError::NotInitialized)
+    }
+
+    fn write_treasury_balance(env: &Env, token: &Address, balance: i128) {
+        env.storage()
+            .instance()
+            .set(&DataKey::Treasury(token.clone()), &balance);
+    }
+
+    /// Count an employee's current annual salary (nothing while stopped)
+    /// toward their payout token's committed payroll, in place of what was
+    /// counted for them before
+    fn commit_salary(env: &Env, employee: &Employee) -> Result<(), SalaryError> {
+        Self::uncommit_salary(env, employee);
+        if !employee.is_active {
+            return Ok(());
+        }
+
+        let policy = Self::employee_increment_policy(env, employee)?;
+        let salary = Self::annual_salary_at(env, employee, env.ledger().timestamp(), &policy);
+        let key = DataKey::CommittedSalary(employee.address.clone());
+        env.storage().persistent().set(&key, &salary);
+        Self::extend_persistent(env, &key);
+        Self::add_committed_payroll(env, &employee.payout_token, salary);
+        Ok(())
+    }
+
+    /// Take what was counted for an employee out of their payout token's
+    /// committed payroll
+    fn uncommit_salary(env: &Env, employee: &Employee) {
+        let key = DataKey::CommittedSalary(employee.address.clone());
+        if let Some(salary) = env.storage().persistent().get::<_, i128>(&key) {
+            env.storage().persistent().remove(&key);
+            Self::add_committed_payroll(env, &employee.payout_token, -salary);
+        }
+    }
+
+    fn add_committed_payroll(env: &Env, token: &Address, amount: i128) {
+        let total = Self::get_committed_payroll(env.clone(), token.clone()) + amount;
+        env.storage()
+            .instance()
+            .set(&DataKey::CommittedPayroll(token.clone()), &total);
+    }
+
+    fn require_accepted_token(env: &Env, token: &Address) -> Result<(), SalaryError> {
+        let tokens: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AcceptedTokens)
+            .ok_or(SalaryError::NotInitialized)?;
+        if tokens.contains(token) {
+            Ok(())
+        } else {
+            Err(SalaryError::TokenNotAccepted)
+        }
+    }
+
+    fn read_employee(env: &Env, employee_address: &Address) -> Result<Employee, SalaryError> {
+        let key = DataKey::Employee(employee_address.clone());
+        match env.storage().persistent().get(&key) {
+            Some(employee) => {
+                Self::extend_persistent(env, &key);
+                Ok(employee)
+            }
+            None if env
+                .storage()
+                .persistent()
+                .has(&DataKey::Termination(employee_address.clone())) =>
+            {
+                Err(SalaryError::EmployeeTerminated)
+            }
+            None => Err(SalaryError::EmployeeNotFound),
+        }
+    }
+
+    fn write_employee(env: &Env, employee: &Employee) {
+        let key = DataKey::Employee(employee.address.clone());
+        env.storage().persistent().set(&key, employee);
+        Self::extend_persistent(env, &key);
+    }
+
+    /// Create the record of a new hire and append them to the employee list
+    fn insert_employee(
+        env: &Env,
+        employee_address: &Address,
+        name: &String,
+        base_salary: i128,
+    ) -> Result<(), SalaryError> {
+        if base_salary <= 0 {
+            return Err(SalaryError::InvalidAmount);
+        }
+        // New hires would take list positions the migration still has to fill
+        if env.storage().instance().has(&DataKey::EmployeeList) {
+            return Err(SalaryError::MigrationPending);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Employee(employee_address.clone()))
+        {
+            return Err(SalaryError::EmployeeAlreadyExists);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Termination(employee_address.clone()))
+        {
+            return Err(SalaryError::EmployeeTerminated);
+        }
+
+        let (current_year, current_month) = Self::get_current_year_month(env);
+
+        let employee = Employee {
+            address: employee_address.clone(),
+            name: name.clone(),
+            base_salary,
+            start_year: current_year,
+            start_month: current_month,
+            last_payment_year: current_year,
+            last_payment_month: 0,
+            is_active: true,
+            suspended_at: 0,
+            inactive_spans: Vec::new(env),
+            start_timestamp: env.ledger().timestamp(),
+            streaming: false,
+            accrual_checkpoint: 0,
+            accrued_balance: 0,
+            paid_through: 0,
+            payout_token: Self::read_token_address(env)?,
+            department: String::from_str(env, ""),
+        };
+
+        Self::write_employee(env, &employee);
+        Self::commit_salary(env, &employee)?;
+
+        // Add to employee list
+        let count = Self::employee_count(env);
+        Self::set_employee_at(env, count, employee_address);
+        env.storage()
+            .instance()
+            .set(&DataKey::EmployeeCount, &(count + 1));
+
+        events::EmployeeAdded {
+            employee: employee_address.clone(),
+            name: name.clone(),
+            base_salary,
+            payout_token: employee.payout_token,
+        }
+        .publish(env);
+        Ok(())
+    }
+
+    /// Current record for a legacy one, paid through the end of its last
+    /// paid month and treated as hired on the first of its start month
+    fn employee_from_legacy(env: &Env, legacy: LegacyEmployee) -> Result<Employee, SalaryError> {
+        let paid_through = if legacy.last_payment_month == 0 {
+            0
+        } else {
+            let (year, month) = calendar::year_month_from_index(
+                calendar::month_index(legacy.last_payment_year, legacy.last_payment_month) + 1,
+            );
+            calendar::timestamp_from_date(year, month, 1)
+                .ok_or(SalaryError::InvalidEffectiveDate)?
+        };
+
+        Ok(Employee {
+            address: legacy.address,
+            name: legacy.name,
+            base_salary: legacy.base_salary,
+            start_year: legacy.start_year,
+            start_month: legacy.start_month,
+            last_payment_year: legacy.last_payment_year,
+            last_payment_month: legacy.last_payment_month,
+            is_active: legacy.is_active,
+            suspended_at: 0,
+            inactive_spans: Vec::new(env),
+            start_timestamp: calendar::timestamp_from_date(
+                legacy.start_year,
+                legacy.start_month,
+                1,
+            )
+            .ok_or(SalaryError::InvalidEffectiveDate)?,
+            streaming: false,
+            accrual_checkpoint: 0,
+            accrued_balance: 0,
+            paid_through,
+            payout_token: Self::read_token_address(env)?,
+            department: String::from_str(env, ""),
+        })
+    }
+
+    /// Bring the configuration of a deployment that predates per-token
+    /// treasuries and basis points up to date, once
+    fn migrate_legacy_config(env: &Env) -> Result<(), SalaryError> {
+        if env.storage().instance().has(&DataKey::LegacyConfigMigrated) {
+            return Ok(());
+        }
+
+        let token = Self::read_token_address(env)?;
+        if let Some(balance) = env
+            .storage()
+            .instance()
+            .get::<_, i128>(&LegacyDataKey::TreasuryBalance)
+        {
+            env.storage().instance().remove(&LegacyDataKey::TreasuryBalance);
+            let total = Self::get_treasury_balance(env.clone(), token.clone()) + balance;
+            Self::write_treasury_balance(env, &token, total);
+        }
+        let mut tokens = Self::get_accepted_tokens(env.clone());
+        if !tokens.contains(&token) {
+            tokens.push_back(token);
+            env.storage()
+                .instance()
+                .set(&DataKey::AcceptedTokens, &tokens);
+        }
+
+        // Legacy increments were whole percentages
+        let percent: u32 = env
+            .storage()
             .instance()
             .get(&DataKey::AnnualIncrement)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::AnnualIncrement, &percent.saturating_mul(100));
+
+        env.storage()
+            .instance()
+            .set(&DataKey::LegacyConfigMigrated, &true);
+        Ok(())
+    }
+
+    /// Insert a salary change into an employee's compensation history
+    fn record_salary_change(
+        env: &Env,
+        employee_address: &Address,
+        new_base_salary: i128,
+        effective_year: u64,
+        effective_month: u32,
+        reason: String,
+    ) -> Result<SalaryChange, SalaryError> {
+        if new_base_salary <= 0 {
+            return Err(SalaryError::InvalidAmount);
+        }
+        if !(1..=12).contains(&effective_month) {
+            return Err(SalaryError::InvalidEffectiveDate);
+        }
+
+        let employee = Self::read_employee(env, employee_address)?;
+        if (effective_year, effective_month) < (employee.start_year, employee.start_month) {
+            return Err(SalaryError::InvalidEffectiveDate);
+        }
+        let effective_from = calendar::timestamp_from_date(effective_year, effective_month, 1)
+            .ok_or(SalaryError::InvalidEffectiveDate)?;
+        let settled_through = if employee.streaming {
+            employee.accrual_checkpoint
+        } else {
+            employee.paid_through
+        };
+        if effective_from < settled_through {
+            return Err(SalaryError::InvalidEffectiveDate);
+        }
+
+        let change = SalaryChange {
+            base_salary: new_base_salary,
+            effective_from,
+            reason: reason.clone(),
+            recorded_at: env.ledger().timestamp(),
+        };
+
+        // Keep the history ordered by effective date; a later change for the
+        // same month supersedes the earlier one
+        let mut history = Self::get_compensation_history(env.clone(), employee_address.clone());
+        let mut position = history.len();
+        while position > 0 && history.get_unchecked(position - 1).effective_from > effective_from {
+            position -= 1;
+        }
+        history.insert(position, change.clone());
+
+        let key = DataKey::CompensationHistory(employee_address.clone());
+        env.storage().persistent().set(&key, &history);
+        Self::extend_persistent(env, &key);
+        Self::commit_salary(env, &employee)?;
+
+        events::SalaryUpdated {
+            employee: employee_address.clone(),
+            base_salary: new_base_salary,
+            effective_from,
+            reason,
+        }
+        .publish(env);
+        Ok(change)
+    }
+
+
+    fn employee_count(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::EmployeeCount)
+            .unwrap_or(0)
+    }
+
+    fn matches_filter(employee: &Employee, filter: &Option<EmployeeFilter>) -> bool {
+        let filter = match filter {
+            Some(filter) => filter,
+            None => return true,
+        };
+
+        let status_matches = match filter.status {
+            StatusFilter::Any => true,
+            StatusFilter::Active => employee.is_active,
+            StatusFilter::Inactive => !employee.is_active,
+        };
+        status_matches
+            && (filter.department.is_empty() || filter.department == employee.department)
+            && employee.start_year >= filter.hired_from_year
+            && (filter.hired_to_year == 0 || employee.start_year <= filter.hired_to_year)
+    }
+
+    /// Employee at a list position, read without extending its TTL so views
+    /// scanning the list don't pay for it
+    fn peek_employee_at(env: &Env, index: u32) -> Result<Employee, SalaryError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Employee(Self::employee_at(env, index)))
+            .ok_or(SalaryError::EmployeeNotFound)
+    }
+
+    fn employee_at(env: &Env, index: u32) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EmployeeAt(index))
             .unwrap()
     }
 
-    /// Helper function to get current year and month (using ledger timestamp)
+    fn set_employee_at(env: &Env, index: u32, employee_address: &Address) {
+        let key = DataKey::EmployeeAt(index);
+        env.storage().persistent().set(&key, employee_address);
+        Self::extend_persistent(env, &key);
+
+        let key = DataKey::EmployeeIndex(employee_address.clone());
+        env.storage().persistent().set(&key, &index);
+        Self::extend_persistent(env, &key);
+    }
+
+    /// Take an employee off the list, moving the last employee into their position
+    fn remove_from_list(env: &Env, employee_address: &Address) {
+        let index_key = DataKey::EmployeeIndex(employee_address.clone());
+        let index: u32 = env.storage().persistent().get(&index_key).unwrap();
+        let last = Self::employee_count(env) - 1;
+
+        if index != last {
+            Self::set_employee_at(env, index, &Self::employee_at(env, last));
+        }
+        env.storage().persistent().remove(&DataKey::EmployeeAt(last));
+        env.storage().persistent().remove(&index_key);
+        env.storage().instance().set(&DataKey::EmployeeCount, &last);
+    }
+
+    fn terminated_count(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TerminatedCount)
+            .unwrap_or(0)
+    }
+
+    /// Extend the TTL of an employee's payslip list and of every payslip on it
+    fn extend_payslips(env: &Env, employee_address: &Address) {
+        let key = DataKey::EmployeePayslips(employee_address.clone());
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        for id in ids.iter() {
+            Self::extend_persistent(env, &DataKey::Payslip(id));
+        }
+        Self::extend_persistent(env, &key);
+    }
+
+    /// Extend a persistent entry's TTL per the TTL config, if the entry exists
+    fn extend_persistent(env: &Env, key: &DataKey) {
+        if env.storage().persistent().has(key) {
+            let config = Self::get_ttl_config(env.clone());
+            env.storage()
+                .persistent()
+                .extend_ttl(key, config.threshold, config.extend_to);
+        }
+    }
+
+    /// Helper function to get current UTC year and month (using ledger timestamp)
     fn get_current_year_month(env: &Env) -> (u64, u32) {
-        let timestamp = env.ledger().timestamp();
-        let total_days = timestamp / (24 * 60 * 60);
-        let year = 1970 + (total_days / 365);
-        let days_in_year = total_days % 365;
-        let month = 1 + (days_in_year / 30).min(11);
-        (year, month as u32)
+        let (year, month, _) = calendar::date_from_timestamp(env.ledger().timestamp());
+        (year, month)
     }
 
     /// Helper function to get current year (using ledger timestamp)
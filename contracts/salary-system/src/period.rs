@@ -0,0 +1,79 @@
+//! Pay periods derived from the ledger timestamp. Each frequency numbers its
+//! periods sequentially so "already paid" and arrears checks reduce to
+//! comparing period indices.
+
+use soroban_sdk::contracttype;
+
+use crate::calendar::{self, SECONDS_PER_DAY};
+
+/// Weeks start on Monday; 1970-01-01 was a Thursday
+const EPOCH_WEEKDAY_OFFSET: u64 = 3;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum PayFrequency {
+    Weekly,
+    BiWeekly,
+    SemiMonthly,              // 1st-15th and 16th-end of month
+    Monthly,
+}
+
+impl PayFrequency {
+    pub fn periods_per_year(self) -> i128 {
+        match self {
+            PayFrequency::Weekly => 52,
+            PayFrequency::BiWeekly => 26,
+            PayFrequency::SemiMonthly => 24,
+            PayFrequency::Monthly => 12,
+        }
+    }
+
+    /// Index of the period containing `timestamp`
+    pub fn period_index(self, timestamp: u64) -> u64 {
+        let days = timestamp / SECONDS_PER_DAY;
+        match self {
+            PayFrequency::Weekly => (days + EPOCH_WEEKDAY_OFFSET) / 7,
+            PayFrequency::BiWeekly => (days + EPOCH_WEEKDAY_OFFSET) / 14,
+            PayFrequency::SemiMonthly => {
+                let (year, month, day) = calendar::civil_from_days(days);
+                calendar::month_index(year, month) * 2 + if day > 15 { 1 } else { 0 }
+            }
+            PayFrequency::Monthly => {
+                let (year, month, _) = calendar::civil_from_days(days);
+                calendar::month_index(year, month)
+            }
+        }
+    }
+
+    /// Timestamp at which period `index` starts (clamped to the range of
+    /// ledger timestamps)
+    pub fn period_start(self, index: u64) -> u64 {
+        match self {
+            PayFrequency::Weekly => {
+                (index * 7).saturating_sub(EPOCH_WEEKDAY_OFFSET) * SECONDS_PER_DAY
+            }
+            PayFrequency::BiWeekly => {
+                (index * 14).saturating_sub(EPOCH_WEEKDAY_OFFSET) * SECONDS_PER_DAY
+            }
+            PayFrequency::SemiMonthly => {
+                let (year, month) = calendar::year_month_from_index(index / 2);
+                let day = if index.is_multiple_of(2) { 1 } else { 16 };
+                calendar::timestamp_from_date(year, month, day).unwrap_or(u64::MAX)
+            }
+            PayFrequency::Monthly => {
+                let (year, month) = calendar::year_month_from_index(index);
+                calendar::timestamp_from_date(year, month, 1).unwrap_or(u64::MAX)
+            }
+        }
+    }
+
+    /// Index of the first period starting at or after `timestamp`
+    pub fn first_period_from(self, timestamp: u64) -> u64 {
+        let index = self.period_index(timestamp);
+        if self.period_start(index) < timestamp {
+            index + 1
+        } else {
+            index
+        }
+    }
+}
@@ -1,9 +1,13 @@
 #![cfg(test)]
 
+extern crate std;
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger, LedgerInfo},
-    token, Address, Env, String,
+    testutils::{
+        storage::Persistent as _, Address as _, EnvTestConfig, Events as _, IssuerFlags, Ledger,
+        MockAuth, MockAuthInvoke,
+    },
+    token, vec, Address, Env, Event, IntoVal, String, U256,
 };
 
 fn create_token_contract<'a>(env: &Env, admin: &Address) -> (token::Client<'a>, token::StellarAssetClient<'a>) {
@@ -14,18 +18,24 @@ fn create_token_contract<'a>(env: &Env, admin: &Address) -> (token::Client<'a>,
     )
 }
 
+fn assert_last_event(env: &Env, contract_id: &Address, event: impl Event) {
+    let all = env.events().all();
+    assert_eq!(
+        all.slice(all.len() - 1..),
+        vec![env, (contract_id.clone(), event.topics(env), event.data(env))]
+    );
+}
+
+fn set_date(env: &Env, year: u64, month: u32, day: u32) {
+    env.ledger()
+        .set_timestamp(calendar::timestamp_from_date(year, month, day).unwrap());
+}
+
 fn advance_months(env: &Env, months: u32) {
-    let seconds_per_month = 30 * 24 * 60 * 60;
-    env.ledger().set(LedgerInfo {
-        timestamp: env.ledger().timestamp() + (months as u64 * seconds_per_month),
-        protocol_version: 20,
-        sequence_number: env.ledger().sequence(),
-        network_id: Default::default(),
-        base_reserve: 10,
-        min_temp_entry_ttl: 10,
-        min_persistent_entry_ttl: 10,
-        max_entry_ttl: 3110400,
-    });
+    let (year, month, day) = calendar::date_from_timestamp(env.ledger().timestamp());
+    let total_months = year * 12 + (month - 1) as u64 + months as u64;
+    let (year, month) = (total_months / 12, (total_months % 12) as u32 + 1);
+    set_date(env, year, month, day.min(calendar::days_in_month(year, month)));
 }
 
 #[test]
@@ -40,10 +50,10 @@ fn test_initialize() {
     let contract_id = env.register(SalarySystemContract, ());
     let client = SalarySystemContractClient::new(&env, &contract_id);
 
-    client.initialize(&admin, &5, &token_client.address);
+    client.initialize(&admin, &500, &token_client.address);
 
-    assert_eq!(client.get_annual_increment(), 5);
-    assert_eq!(client.get_treasury_balance(), 0);
+    assert_eq!(client.get_annual_increment(), 500);
+    assert_eq!(client.get_treasury_balance(&token_client.address), 0);
 }
 
 #[test]
@@ -61,10 +71,10 @@ fn test_fund_treasury() {
     let contract_id = env.register(SalarySystemContract, ());
     let client = SalarySystemContractClient::new(&env, &contract_id);
 
-    client.initialize(&admin, &5, &token_client.address);
-    client.fund_treasury(&funder, &5_000_000);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
 
-    assert_eq!(client.get_treasury_balance(), 5_000_000);
+    assert_eq!(client.get_treasury_balance(&token_client.address), 5_000_000);
 }
 
 #[test]
@@ -80,10 +90,10 @@ fn test_add_employee() {
     let contract_id = env.register(SalarySystemContract, ());
     let client = SalarySystemContractClient::new(&env, &contract_id);
 
-    client.initialize(&admin, &5, &token_client.address);
+    client.initialize(&admin, &500, &token_client.address);
 
     let employee_name = String::from_str(&env, "Alice");
-    client.add_employee(&employee1, &employee_name, &1_200_000);
+    client.add_employee(&admin, &employee1, &employee_name, &1_200_000);
 
     let employee_data = client.get_employee(&employee1);
     assert_eq!(employee_data.name, employee_name);
@@ -106,10 +116,10 @@ fn test_monthly_salary_calculation() {
     let contract_id = env.register(SalarySystemContract, ());
     let client = SalarySystemContractClient::new(&env, &contract_id);
 
-    client.initialize(&admin, &10, &token_client.address);
+    client.initialize(&admin, &1000, &token_client.address);
 
     let employee_name = String::from_str(&env, "Bob");
-    client.add_employee(&employee1, &employee_name, &1_200_000);
+    client.add_employee(&admin, &employee1, &employee_name, &1_200_000);
 
     let monthly_salary = client.calculate_current_salary(&employee1);
     assert_eq!(monthly_salary, 100_000);
@@ -131,16 +141,16 @@ fn test_pay_monthly_salary() {
     let contract_id = env.register(SalarySystemContract, ());
     let client = SalarySystemContractClient::new(&env, &contract_id);
 
-    client.initialize(&admin, &5, &token_client.address);
-    client.fund_treasury(&funder, &5_000_000);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
 
     let employee_name = String::from_str(&env, "Charlie");
-    client.add_employee(&employee1, &employee_name, &1_200_000);
+    client.add_employee(&admin, &employee1, &employee_name, &1_200_000);
 
-    client.pay_salary(&employee1);
+    client.pay_salary(&admin, &employee1);
 
     assert_eq!(token_client.balance(&employee1), 100_000);
-    assert_eq!(client.get_treasury_balance(), 4_900_000);
+    assert_eq!(client.get_treasury_balance(&token_client.address), 4_900_000);
 }
 
 #[test]
@@ -159,23 +169,23 @@ fn test_pay_multiple_months() {
     let contract_id = env.register(SalarySystemContract, ());
     let client = SalarySystemContractClient::new(&env, &contract_id);
 
-    client.initialize(&admin, &5, &token_client.address);
-    client.fund_treasury(&funder, &5_000_000);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
 
     let employee_name = String::from_str(&env, "Dave");
-    client.add_employee(&employee1, &employee_name, &1_200_000);
+    client.add_employee(&admin, &employee1, &employee_name, &1_200_000);
 
-    client.pay_salary(&employee1);
+    client.pay_salary(&admin, &employee1);
     assert_eq!(token_client.balance(&employee1), 100_000);
 
     advance_months(&env, 1);
 
-    client.pay_salary(&employee1);
+    client.pay_salary(&admin, &employee1);
     assert_eq!(token_client.balance(&employee1), 200_000);
 
     advance_months(&env, 1);
 
-    client.pay_salary(&employee1);
+    client.pay_salary(&admin, &employee1);
     assert_eq!(token_client.balance(&employee1), 300_000);
 }
 
@@ -196,11 +206,11 @@ fn test_sustainability_years_calculation() {
     let contract_id = env.register(SalarySystemContract, ());
     let client = SalarySystemContractClient::new(&env, &contract_id);
 
-    client.initialize(&admin, &10, &token_client.address);
-    client.fund_treasury(&funder, &10_000_000);
+    client.initialize(&admin, &1000, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &10_000_000);
 
-    client.add_employee(&employee1, &String::from_str(&env, "Alice"), &1_200_000);
-    client.add_employee(&employee2, &String::from_str(&env, "Bob"), &1_800_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
+    client.add_employee(&admin, &employee2, &String::from_str(&env, "Bob"), &1_800_000);
 
     let years = client.calculate_sustainability_years();
     assert!(years >= 2);
@@ -208,7 +218,6 @@ fn test_sustainability_years_calculation() {
 }
 
 #[test]
-#[should_panic(expected = "Insufficient treasury balance")]
 fn test_insufficient_treasury() {
     let env = Env::default();
     env.mock_all_auths();
@@ -224,16 +233,19 @@ fn test_insufficient_treasury() {
     let contract_id = env.register(SalarySystemContract, ());
     let client = SalarySystemContractClient::new(&env, &contract_id);
 
-    client.initialize(&admin, &5, &token_client.address);
-    client.fund_treasury(&funder, &50_000);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &50_000);
 
-    client.add_employee(&employee1, &String::from_str(&env, "Alice"), &1_200_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
 
-    client.pay_salary(&employee1);
+    assert_eq!(
+        client.try_pay_salary(&admin, &employee1),
+        Err(Ok(SalaryError::InsufficientTreasury))
+    );
+    assert_eq!(client.get_treasury_balance(&token_client.address), 50_000);
 }
 
 #[test]
-#[should_panic(expected = "Salary already paid for this month")]
 fn test_double_payment_same_month() {
     let env = Env::default();
     env.mock_all_auths();
@@ -249,13 +261,16 @@ fn test_double_payment_same_month() {
     let contract_id = env.register(SalarySystemContract, ());
     let client = SalarySystemContractClient::new(&env, &contract_id);
 
-    client.initialize(&admin, &5, &token_client.address);
-    client.fund_treasury(&funder, &5_000_000);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
 
-    client.add_employee(&employee1, &String::from_str(&env, "Alice"), &1_200_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
 
-    client.pay_salary(&employee1);
-    client.pay_salary(&employee1);
+    client.pay_salary(&admin, &employee1);
+    assert_eq!(
+        client.try_pay_salary(&admin, &employee1),
+        Err(Ok(SalaryError::SalaryAlreadyPaid))
+    );
 }
 
 #[test]
@@ -276,21 +291,21 @@ fn test_employee_claim_salary() {
     let client = SalarySystemContractClient::new(&env, &contract_id);
 
     // Initialize contract
-    client.initialize(&admin, &5, &token_client.address);
+    client.initialize(&admin, &500, &token_client.address);
     
     // Fund treasury
-    client.fund_treasury(&funder, &5_000_000);
-    assert_eq!(client.get_treasury_balance(), 5_000_000);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    assert_eq!(client.get_treasury_balance(&token_client.address), 5_000_000);
 
     // Add employee with 1,200,000 stroops/month base salary
-    client.add_employee(&employee1, &String::from_str(&env, "Alice"), &1_200_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
 
     // Employee claims their own salary
     client.claim_salary(&employee1);
 
     // Verify salary was paid
     assert_eq!(token_client.balance(&employee1), 100_000); // 1,200,000 / 12 = 100,000 monthly
-    assert_eq!(client.get_treasury_balance(), 4_900_000); // 5,000,000 - 100,000
+    assert_eq!(client.get_treasury_balance(&token_client.address), 4_900_000); // 5,000,000 - 100,000
 
     // Verify employee record was updated
     let employee_info = client.get_employee(&employee1);
@@ -298,7 +313,6 @@ fn test_employee_claim_salary() {
 }
 
 #[test]
-#[should_panic(expected = "Salary already paid for this month")]
 fn test_employee_cannot_claim_twice_same_month() {
     let env = Env::default();
     env.mock_all_auths();
@@ -314,13 +328,3695 @@ fn test_employee_cannot_claim_twice_same_month() {
     let contract_id = env.register(SalarySystemContract, ());
     let client = SalarySystemContractClient::new(&env, &contract_id);
 
-    client.initialize(&admin, &5, &token_client.address);
-    client.fund_treasury(&funder, &5_000_000);
-    client.add_employee(&employee1, &String::from_str(&env, "Alice"), &1_200_000);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
 
     // First claim succeeds
     client.claim_salary(&employee1);
     
     // Second claim in same month should fail
+    assert_eq!(
+        client.try_claim_salary(&employee1),
+        Err(Ok(SalaryError::SalaryAlreadyPaid))
+    );
+}
+
+#[test]
+fn test_calendar_round_trip() {
+    // Every day from 1970 through 2199 converts back to the same day number
+    let mut expected_year = 1970;
+    let mut expected_month = 1;
+    let mut expected_day = 1;
+    for days in 0..calendar::days_from_civil(2200, 1, 1).unwrap() {
+        let (year, month, day) = calendar::civil_from_days(days);
+        assert_eq!((year, month, day), (expected_year, expected_month, expected_day));
+        assert_eq!(calendar::days_from_civil(year, month, day), Some(days));
+
+        expected_day += 1;
+        if expected_day > calendar::days_in_month(expected_year, expected_month) {
+            expected_day = 1;
+            expected_month += 1;
+            if expected_month > 12 {
+                expected_month = 1;
+                expected_year += 1;
+            }
+        }
+    }
+
+    assert!(calendar::is_leap_year(2000));
+    assert!(calendar::is_leap_year(2024));
+    assert!(!calendar::is_leap_year(2100));
+    assert_eq!(calendar::days_in_month(2024, 2), 29);
+    assert_eq!(calendar::days_in_month(2023, 2), 28);
+
+    // Dates before the epoch, invalid dates and unrepresentable years
+    assert_eq!(calendar::days_from_civil(1969, 12, 31), None);
+    assert_eq!(calendar::days_from_civil(0, 1, 1), None);
+    assert_eq!(calendar::days_from_civil(2024, 13, 1), None);
+    assert_eq!(calendar::days_from_civil(2023, 2, 29), None);
+    assert_eq!(calendar::timestamp_from_date(u64::MAX, 7, 1), None);
+    assert_eq!(calendar::anniversary(0, u64::MAX), None);
+}
+
+#[test]
+fn test_current_year_month_known_dates() {
+    let env = Env::default();
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    // 2024-02-29T00:00:00Z
+    env.ledger().set_timestamp(1_709_164_800);
+    assert_eq!((client.get_year(), client.get_month()), (2024, 2));
+
+    // 2024-12-31T23:59:59Z is still December, one second later is January
+    env.ledger().set_timestamp(1_735_689_599);
+    assert_eq!((client.get_year(), client.get_month()), (2024, 12));
+    env.ledger().set_timestamp(1_735_689_600);
+    assert_eq!((client.get_year(), client.get_month()), (2025, 1));
+
+    // 2023-03-01T00:00:00Z follows a 28-day February
+    env.ledger().set_timestamp(1_677_628_800);
+    assert_eq!((client.get_year(), client.get_month()), (2023, 3));
+
+    // 1970-12-31 was previously bucketed as 1971
+    set_date(&env, 1970, 12, 31);
+    assert_eq!((client.get_year(), client.get_month()), (1970, 12));
+}
+
+#[test]
+fn test_pay_salary_on_consecutive_calendar_days() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2024, 2, 29);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
+
+    let employee_info = client.get_employee(&employee1);
+    assert_eq!((employee_info.start_year, employee_info.start_month), (2024, 2));
+
+    // Hired on the last day of February: one of its 29 days is paid
+    client.pay_salary(&admin, &employee1);
+    assert_eq!(token_client.balance(&employee1), 3_448);
+
+    // The next day is March, so a new month's salary is due
+    set_date(&env, 2024, 3, 1);
+    client.claim_salary(&employee1);
+    assert_eq!(token_client.balance(&employee1), 103_448);
+
+    let employee_info = client.get_employee(&employee1);
+    assert_eq!(
+        (employee_info.last_payment_year, employee_info.last_payment_month),
+        (2024, 3)
+    );
+}
+
+#[test]
+fn test_initialize_twice_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, _) = create_token_contract(&env, &token_admin);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+    assert_eq!(
+        client.try_initialize(&admin, &1000, &token_client.address),
+        Err(Ok(SalaryError::AlreadyInitialized))
+    );
+    assert_eq!(client.get_annual_increment(), 500);
+}
+
+#[test]
+fn test_uninitialized_contract_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token = Address::generate(&env);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    assert_eq!(
+        client.try_add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000),
+        Err(Ok(SalaryError::NotInitialized))
+    );
+    assert_eq!(
+        client.try_pay_salary(&admin, &employee1),
+        Err(Ok(SalaryError::NotInitialized))
+    );
+    assert_eq!(
+        client.try_fund_treasury(&funder, &token, &1_000),
+        Err(Ok(SalaryError::NotInitialized))
+    );
+    assert_eq!(
+        client.try_get_annual_increment(),
+        Err(Ok(SalaryError::NotInitialized))
+    );
+}
+
+#[test]
+fn test_employee_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+
+    assert_eq!(
+        client.try_add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &0),
+        Err(Ok(SalaryError::InvalidAmount))
+    );
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
+    assert_eq!(
+        client.try_add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000),
+        Err(Ok(SalaryError::EmployeeAlreadyExists))
+    );
+    assert_eq!(client.get_all_employees().len(), 1);
+
+    assert_eq!(
+        client.try_get_employee(&stranger),
+        Err(Ok(SalaryError::EmployeeNotFound))
+    );
+    assert_eq!(
+        client.try_claim_salary(&stranger),
+        Err(Ok(SalaryError::EmployeeNotFound))
+    );
+    assert_eq!(
+        client.try_stop_employee_salary(&admin, &stranger),
+        Err(Ok(SalaryError::EmployeeNotFound))
+    );
+
+    client.stop_employee_salary(&admin, &employee1);
+    assert_eq!(
+        client.try_pay_salary(&admin, &employee1),
+        Err(Ok(SalaryError::EmployeeNotActive))
+    );
+    assert_eq!(
+        client.try_claim_salary(&employee1),
+        Err(Ok(SalaryError::EmployeeNotActive))
+    );
+
+    client.resume_employee_salary(&admin, &employee1);
+    client.claim_salary(&employee1);
+    assert_eq!(token_client.balance(&employee1), 100_000);
+
+    assert_eq!(
+        client.try_fund_treasury(&funder, &token_client.address, &-1),
+        Err(Ok(SalaryError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_run_payroll_in_batches() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let dave = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &250_000);
+
+    client.add_employee(&admin, &alice, &String::from_str(&env, "Alice"), &1_200_000);
+    client.add_employee(&admin, &bob, &String::from_str(&env, "Bob"), &1_200_000);
+    client.add_employee(&admin, &carol, &String::from_str(&env, "Carol"), &1_200_000);
+    client.add_employee(&admin, &dave, &String::from_str(&env, "Dave"), &1_200_000);
+
+    client.claim_salary(&alice);
+    client.stop_employee_salary(&admin, &bob);
+
+    let first = client.run_payroll(&admin, &0, &2);
+    assert_eq!(first.next_cursor, Some(2));
+    assert_eq!(first.total_paid, 0);
+    assert_eq!(
+        first.outcomes,
+        vec![
+            &env,
+            PayrollOutcome {
+                employee: alice.clone(),
+                status: PayrollStatus::AlreadyPaid,
+                amount: 0,
+            },
+            PayrollOutcome {
+                employee: bob.clone(),
+                status: PayrollStatus::Inactive,
+                amount: 0,
+            },
+        ]
+    );
+
+    // Only one more salary fits in the treasury; Dave is skipped, not aborted
+    let second = client.run_payroll(&admin, &2, &10);
+    assert_eq!(second.next_cursor, None);
+    assert_eq!(second.total_paid, 100_000);
+    assert_eq!(
+        second.outcomes,
+        vec![
+            &env,
+            PayrollOutcome {
+                employee: carol.clone(),
+                status: PayrollStatus::Paid,
+                amount: 100_000,
+            },
+            PayrollOutcome {
+                employee: dave.clone(),
+                status: PayrollStatus::InsufficientTreasury,
+                amount: 0,
+            },
+        ]
+    );
+    assert_eq!(token_client.balance(&carol), 100_000);
+    assert_eq!(token_client.balance(&dave), 0);
+    assert_eq!(client.get_treasury_balance(&token_client.address), 50_000);
+
+    // Topping up lets a re-run pay only the employee still outstanding
+    client.fund_treasury(&funder, &token_client.address, &100_000);
+    let rerun = client.run_payroll(&admin, &0, &10);
+    assert_eq!(rerun.total_paid, 100_000);
+    assert_eq!(token_client.balance(&dave), 100_000);
+
+    assert_eq!(client.run_payroll(&admin, &10, &10).outcomes.len(), 0);
+    assert_eq!(client.try_run_payroll(&admin, &0, &0), Err(Ok(SalaryError::InvalidLimit)));
+}
+
+#[test]
+fn test_arrears_paid_for_missed_months() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2024, 1, 15);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
+
+    // Nobody runs payroll January (17 of 31 days worked) through April
+    set_date(&env, 2024, 4, 20);
+    assert_eq!(
+        client.get_arrears(&employee1),
+        Arrears {
+            periods: 4,
+            amount: 354_838,
+        }
+    );
+
+    client.pay_salary(&admin, &employee1);
+    assert_eq!(token_client.balance(&employee1), 354_838);
+    assert_eq!(client.get_treasury_balance(&token_client.address), 4_645_162);
+    assert_eq!(client.get_arrears(&employee1), Arrears { periods: 0, amount: 0 });
+    assert_eq!(
+        client.try_claim_salary(&employee1),
+        Err(Ok(SalaryError::SalaryAlreadyPaid))
+    );
+}
+
+#[test]
+fn test_arrears_capped_per_payment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2024, 1, 15);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
+
+    assert_eq!(client.get_max_arrears_periods(), 12);
+    assert_eq!(
+        client.try_set_max_arrears_periods(&0),
+        Err(Ok(SalaryError::InvalidLimit))
+    );
+    client.set_max_arrears_periods(&2);
+
+    set_date(&env, 2024, 4, 20);
+    client.claim_salary(&employee1);
+    assert_eq!(token_client.balance(&employee1), 54_838 + 100_000);
+
+    let employee_info = client.get_employee(&employee1);
+    assert_eq!(
+        (employee_info.last_payment_year, employee_info.last_payment_month),
+        (2024, 2)
+    );
+
+    client.claim_salary(&employee1);
+    assert_eq!(token_client.balance(&employee1), 354_838);
+    assert_eq!(
+        client.try_claim_salary(&employee1),
+        Err(Ok(SalaryError::SalaryAlreadyPaid))
+    );
+}
+
+#[test]
+fn test_arrears_skip_inactive_spans() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2024, 1, 15);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
+    client.pay_salary(&admin, &employee1);
+
+    // Stopped on February 10th: the 9 days before are still owed, later months are not
+    set_date(&env, 2024, 2, 10);
+    client.stop_employee_salary(&admin, &employee1);
+    set_date(&env, 2024, 4, 1);
+    assert_eq!(
+        client.get_arrears(&employee1),
+        Arrears {
+            periods: 1,
+            amount: 31_034,
+        }
+    );
+
+    // Resumed on May 5th: March and April were inactive throughout, and
+    // May is owed from the 5th (27 of 31 days)
+    set_date(&env, 2024, 5, 5);
+    client.resume_employee_salary(&admin, &employee1);
+    assert_eq!(
+        client.get_arrears(&employee1),
+        Arrears {
+            periods: 2,
+            amount: 31_034 + 87_096,
+        }
+    );
+
+    client.pay_salary(&admin, &employee1);
+    assert_eq!(token_client.balance(&employee1), 54_838 + 31_034 + 87_096);
+    assert_eq!(client.get_employee(&employee1).inactive_spans.len(), 0);
+}
+
+#[test]
+fn test_period_partly_stopped_is_prorated() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2025, 2, 1);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
+    client.pay_salary(&admin, &employee1);
+
+    // Stopped from the 2nd to the 28th: March 1st and 28th-31st are owed
+    set_date(&env, 2025, 3, 2);
+    client.stop_employee_salary(&admin, &employee1);
+    set_date(&env, 2025, 3, 28);
+    client.resume_employee_salary(&admin, &employee1);
+    assert_eq!(
+        client.get_arrears(&employee1),
+        Arrears {
+            periods: 1,
+            amount: 16_129,
+        }
+    );
+
     client.claim_salary(&employee1);
+    assert_eq!(token_client.balance(&employee1), 100_000 + 16_129);
+
+    // April is paid in full again
+    set_date(&env, 2025, 4, 10);
+    client.claim_salary(&employee1);
+    assert_eq!(token_client.balance(&employee1), 200_000 + 16_129);
+}
+
+#[test]
+fn test_arrears_use_salary_of_each_year() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2024, 11, 1);
+    client.initialize(&admin, &1000, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
+
+    // No raise in January: the first one comes on the hire anniversary
+    set_date(&env, 2025, 2, 3);
+    assert_eq!(
+        client.get_arrears(&employee1),
+        Arrears {
+            periods: 4,
+            amount: 400_000,
+        }
+    );
+    client.pay_salary(&admin, &employee1);
+    assert_eq!(token_client.balance(&employee1), 400_000);
+
+    // March to October at 100,000; November and December after the 10% raise
+    set_date(&env, 2025, 12, 3);
+    assert_eq!(
+        client.get_arrears(&employee1),
+        Arrears {
+            periods: 10,
+            amount: 1_020_000,
+        }
+    );
+    client.pay_salary(&admin, &employee1);
+    assert_eq!(token_client.balance(&employee1), 1_420_000);
+}
+
+#[test]
+fn test_streaming_withdraw_accrued() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    // 2024 has 366 days, so 3,660,000 a year vests 10,000 a day
+    set_date(&env, 2024, 1, 1);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &3_660_000);
+
+    assert_eq!(client.try_get_accrued(&employee1), Err(Ok(SalaryError::NotStreaming)));
+    client.enable_streaming(&admin, &employee1);
+
+    set_date(&env, 2024, 1, 11);
+    assert_eq!(client.get_accrued(&employee1), 100_000);
+
+    // Half a day later
+    env.ledger().set_timestamp(env.ledger().timestamp() + 12 * 60 * 60);
+    assert_eq!(client.get_accrued(&employee1), 105_000);
+
+    client.withdraw_accrued(&employee1, &60_000);
+    assert_eq!(token_client.balance(&employee1), 60_000);
+    assert_eq!(client.get_treasury_balance(&token_client.address), 4_940_000);
+    assert_eq!(client.get_accrued(&employee1), 45_000);
+
+    assert_eq!(
+        client.try_withdraw_accrued(&employee1, &50_000),
+        Err(Ok(SalaryError::InsufficientAccrued))
+    );
+    assert_eq!(
+        client.try_withdraw_accrued(&employee1, &0),
+        Err(Ok(SalaryError::InvalidAmount))
+    );
+
+    // Monthly payments no longer apply to a streaming employee
+    assert_eq!(
+        client.try_pay_salary(&admin, &employee1),
+        Err(Ok(SalaryError::EmployeeStreaming))
+    );
+    let run = client.run_payroll(&admin, &0, &10);
+    assert_eq!(run.outcomes.get(0).unwrap().status, PayrollStatus::Streaming);
+}
+
+#[test]
+fn test_streaming_pauses_while_inactive() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2024, 1, 1);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &3_660_000);
+    client.enable_streaming(&admin, &employee1);
+
+    set_date(&env, 2024, 1, 6);
+    client.stop_employee_salary(&admin, &employee1);
+
+    set_date(&env, 2024, 1, 20);
+    assert_eq!(client.get_accrued(&employee1), 50_000);
+
+    client.resume_employee_salary(&admin, &employee1);
+    set_date(&env, 2024, 1, 22);
+    assert_eq!(client.get_accrued(&employee1), 70_000);
+}
+
+#[test]
+fn test_streaming_skips_earlier_inactive_spans() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    // 10,000 a day in 2025
+    set_date(&env, 2025, 1, 1);
+    client.initialize(&admin, &0, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &3_650_000);
+
+    set_date(&env, 2025, 2, 1);
+    client.stop_employee_salary(&admin, &employee1);
+    set_date(&env, 2025, 6, 1);
+    client.resume_employee_salary(&admin, &employee1);
+    assert_eq!(
+        client.get_arrears(&employee1),
+        Arrears {
+            periods: 2,
+            amount: 2 * 304_166,
+        }
+    );
+
+    // Only January accrues from before the switch; February to May were inactive
+    client.enable_streaming(&admin, &employee1);
+    assert_eq!(client.get_accrued(&employee1), 310_000);
+
+    set_date(&env, 2025, 6, 11);
+    assert_eq!(client.get_accrued(&employee1), 410_000);
+    client.withdraw_accrued(&employee1, &410_000);
+    assert_eq!(token_client.balance(&employee1), 410_000);
+}
+
+#[test]
+fn test_streaming_starts_after_paid_month() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2024, 12, 10);
+    client.initialize(&admin, &1000, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &3_650_000);
+    client.pay_salary(&admin, &employee1);
+
+    // December is already paid, so nothing vests until January
+    client.enable_streaming(&admin, &employee1);
+    set_date(&env, 2024, 12, 31);
+    assert_eq!(client.get_accrued(&employee1), 0);
+
+    // No increment until the hire anniversary: 3,650,000 over 365 days
+    set_date(&env, 2025, 1, 11);
+    assert_eq!(client.get_accrued(&employee1), 100_000);
+
+    // From December 10th the 10% increment vests 11,000 a day
+    set_date(&env, 2025, 12, 20);
+    assert_eq!(client.get_accrued(&employee1), 3_430_000 + 110_000);
+}
+
+#[test]
+fn test_pay_period_boundaries() {
+    let monday = calendar::timestamp_from_date(2024, 1, 1).unwrap();
+    let sunday = calendar::timestamp_from_date(2024, 1, 7).unwrap();
+    let next_monday = calendar::timestamp_from_date(2024, 1, 8).unwrap();
+
+    let weekly = PayFrequency::Weekly;
+    assert_eq!(weekly.period_index(monday), weekly.period_index(sunday));
+    assert_eq!(weekly.period_index(next_monday), weekly.period_index(monday) + 1);
+    assert_eq!(weekly.period_start(weekly.period_index(sunday)), monday);
+
+    let bi_weekly = PayFrequency::BiWeekly;
+    let period = bi_weekly.period_index(sunday);
+    assert_eq!(
+        bi_weekly.period_start(period + 1) - bi_weekly.period_start(period),
+        14 * calendar::SECONDS_PER_DAY
+    );
+
+    let semi_monthly = PayFrequency::SemiMonthly;
+    let jan_15 = calendar::timestamp_from_date(2024, 1, 15).unwrap();
+    let jan_16 = calendar::timestamp_from_date(2024, 1, 16).unwrap();
+    assert_eq!(semi_monthly.period_index(jan_16), semi_monthly.period_index(jan_15) + 1);
+    assert_eq!(semi_monthly.period_start(semi_monthly.period_index(jan_15)), monday);
+    assert_eq!(
+        semi_monthly.period_start(semi_monthly.period_index(jan_16) + 1),
+        calendar::timestamp_from_date(2024, 2, 1).unwrap()
+    );
+
+    let monthly = PayFrequency::Monthly;
+    let feb_29 = calendar::timestamp_from_date(2024, 2, 29).unwrap();
+    assert_eq!(
+        monthly.period_start(monthly.period_index(feb_29)),
+        calendar::timestamp_from_date(2024, 2, 1).unwrap()
+    );
+    assert_eq!(monthly.first_period_from(feb_29), monthly.period_index(feb_29) + 1);
+    assert_eq!(monthly.first_period_from(monday), monthly.period_index(monday));
+}
+
+#[test]
+fn test_bi_weekly_employee_override() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    // 2024-01-01 is a Monday
+    set_date(&env, 2024, 1, 1);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &2_600_000);
+
+    client.set_employee_pay_frequency(&admin, &employee1, &Some(PayFrequency::BiWeekly));
+    assert_eq!(client.get_pay_frequency(&employee1), PayFrequency::BiWeekly);
+    assert_eq!(client.get_default_pay_frequency(), PayFrequency::Monthly);
+    assert_eq!(client.calculate_current_salary(&employee1), 100_000);
+
+    client.pay_salary(&admin, &employee1);
+    assert_eq!(token_client.balance(&employee1), 100_000);
+
+    // Still inside the first fortnight
+    set_date(&env, 2024, 1, 14);
+    assert_eq!(
+        client.try_pay_salary(&admin, &employee1),
+        Err(Ok(SalaryError::SalaryAlreadyPaid))
+    );
+
+    set_date(&env, 2024, 1, 15);
+    client.claim_salary(&employee1);
+    assert_eq!(token_client.balance(&employee1), 200_000);
+
+    // Three more fortnights start by Feb 26
+    set_date(&env, 2024, 2, 26);
+    assert_eq!(
+        client.get_arrears(&employee1),
+        Arrears {
+            periods: 3,
+            amount: 300_000,
+        }
+    );
+}
+
+#[test]
+fn test_pay_frequency_change_pays_stub_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let employee2 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    // 2024-01-01 is a Monday
+    set_date(&env, 2024, 1, 1);
+    client.initialize(&admin, &0, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &5_200_000);
+    client.add_employee(&admin, &employee2, &String::from_str(&env, "Bob"), &5_200_000);
+    client.set_employee_pay_frequency(&admin, &employee1, &Some(PayFrequency::Weekly));
+
+    // Weekly to monthly: paid through Jan 21, then Jan 22-31 is 10 of
+    // January's 31 days
+    set_date(&env, 2024, 1, 21);
+    client.pay_salary(&admin, &employee1);
+    assert_eq!(token_client.balance(&employee1), 300_000);
+    client.set_employee_pay_frequency(&admin, &employee1, &None);
+
+    set_date(&env, 2024, 3, 31);
+    assert_eq!(
+        client.get_arrears(&employee1),
+        Arrears {
+            periods: 3,
+            amount: 433_333 * 10 / 31 + 2 * 433_333,
+        }
+    );
+    client.pay_salary(&admin, &employee1);
+    assert_eq!(token_client.balance(&employee1), 300_000 + 433_333 * 10 / 31 + 2 * 433_333);
+
+    // Monthly to weekly: paid through January, then Feb 1-4 is 4 of the
+    // week of Jan 29's 7 days
+    set_date(&env, 2024, 1, 31);
+    client.pay_salary(&admin, &employee2);
+    assert_eq!(token_client.balance(&employee2), 433_333);
+    client.set_employee_pay_frequency(&admin, &employee2, &Some(PayFrequency::Weekly));
+
+    set_date(&env, 2024, 2, 11);
+    assert_eq!(
+        client.get_arrears(&employee2),
+        Arrears {
+            periods: 2,
+            amount: 100_000 * 4 / 7 + 100_000,
+        }
+    );
+    client.pay_salary(&admin, &employee2);
+    assert_eq!(token_client.balance(&employee2), 433_333 + 100_000 * 4 / 7 + 100_000);
+    assert_eq!(
+        client.get_payslips(&employee2, &1, &1).get(0).unwrap().period_start,
+        calendar::timestamp_from_date(2024, 2, 1).unwrap()
+    );
+}
+
+#[test]
+fn test_semi_monthly_default_frequency() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let employee2 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2024, 1, 10);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.set_default_pay_frequency(&PayFrequency::SemiMonthly);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &2_400_000);
+    client.add_employee(&admin, &employee2, &String::from_str(&env, "Bob"), &1_200_000);
+    client.set_employee_pay_frequency(&admin, &employee2, &Some(PayFrequency::Monthly));
+
+    // Jan 10-15 is 6 of the first period's 15 days
+    client.pay_salary(&admin, &employee1);
+    assert_eq!(token_client.balance(&employee1), 40_000);
+    set_date(&env, 2024, 1, 15);
+    assert_eq!(
+        client.try_pay_salary(&admin, &employee1),
+        Err(Ok(SalaryError::SalaryAlreadyPaid))
+    );
+    set_date(&env, 2024, 1, 16);
+    client.pay_salary(&admin, &employee1);
+    assert_eq!(token_client.balance(&employee1), 140_000);
+
+    // Feb 1-15, Feb 16-29 and Mar 1-15 are outstanding
+    set_date(&env, 2024, 3, 2);
+    assert_eq!(client.get_arrears(&employee1).periods, 3);
+    assert_eq!(client.get_arrears(&employee2).periods, 3);
+
+    // Clearing the override falls back to the semi-monthly default
+    client.set_employee_pay_frequency(&admin, &employee2, &None);
+    assert_eq!(client.get_pay_frequency(&employee2), PayFrequency::SemiMonthly);
+    assert_eq!(client.calculate_current_salary(&employee2), 50_000);
+}
+
+#[test]
+fn test_two_step_admin_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let employee2 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
+
+    assert_eq!(client.try_accept_admin(), Err(Ok(SalaryError::NoPendingAdmin)));
+
+    client.propose_admin(&new_admin);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::AdminTransferProposed {
+            admin: admin.clone(),
+            new_admin: new_admin.clone(),
+        },
+    );
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+    assert_eq!(client.get_admin(), admin);
+
+    client.accept_admin();
+    assert_eq!(
+        env.auths()[0].0,
+        new_admin,
+        "the proposed admin must authorize acceptance"
+    );
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::AdminTransferAccepted {
+            previous_admin: admin.clone(),
+            new_admin: new_admin.clone(),
+        },
+    );
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+
+    // The old admin can no longer pay salaries or onboard employees
+    let name = String::from_str(&env, "Bob");
+    let old_admin_pay = client
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "pay_salary",
+                args: (&admin, &employee1).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_pay_salary(&admin, &employee1);
+    assert_eq!(old_admin_pay, Err(Ok(SalaryError::Unauthorized)));
+
+    let old_admin_add = client
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "add_employee",
+                args: (&admin, &employee2, &name, 1_200_000i128).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_add_employee(&admin, &employee2, &name, &1_200_000);
+    assert_eq!(old_admin_add, Err(Ok(SalaryError::Unauthorized)));
+
+    // The new admin can
+    client
+        .mock_auths(&[MockAuth {
+            address: &new_admin,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "pay_salary",
+                args: (&new_admin, &employee1).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .pay_salary(&new_admin, &employee1);
+    assert_eq!(token_client.balance(&employee1), 100_000);
+}
+
+#[test]
+fn test_cancel_admin_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, _) = create_token_contract(&env, &token_admin);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+
+    assert_eq!(
+        client.try_cancel_admin_transfer(),
+        Err(Ok(SalaryError::NoPendingAdmin))
+    );
+
+    client.propose_admin(&new_admin);
+    client.cancel_admin_transfer();
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::AdminTransferCancelled {
+            admin: admin.clone(),
+            new_admin: new_admin.clone(),
+        },
+    );
+
+    assert_eq!(client.get_pending_admin(), None);
+    assert_eq!(client.try_accept_admin(), Err(Ok(SalaryError::NoPendingAdmin)));
+    assert_eq!(client.get_admin(), admin);
+}
+
+#[test]
+fn test_role_based_access() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let hr = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+
+    client.grant_role(&Role::Hr, &hr);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::RoleGranted {
+            account: hr.clone(),
+            role: Role::Hr,
+            admin: admin.clone(),
+        },
+    );
+    client.grant_role(&Role::PayrollOperator, &operator);
+    assert!(client.has_role(&Role::Hr, &hr));
+    assert!(!client.has_role(&Role::PayrollOperator, &hr));
+
+    // HR onboards, but cannot trigger payments
+    client.add_employee(&hr, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
+    assert_eq!(
+        client.try_pay_salary(&hr, &employee1),
+        Err(Ok(SalaryError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_run_payroll(&hr, &0, &10),
+        Err(Ok(SalaryError::Unauthorized))
+    );
+
+    // The payroll operator pays, but cannot change employee status
+    assert_eq!(
+        client.try_stop_employee_salary(&operator, &employee1),
+        Err(Ok(SalaryError::Unauthorized))
+    );
+    client.pay_salary(&operator, &employee1);
+    assert_eq!(token_client.balance(&employee1), 100_000);
+
+    client.stop_employee_salary(&hr, &employee1);
+    assert!(!client.get_employee(&employee1).is_active);
+
+    client.revoke_role(&Role::Hr, &hr);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::RoleRevoked {
+            account: hr.clone(),
+            role: Role::Hr,
+            admin: admin.clone(),
+        },
+    );
+    assert!(!client.has_role(&Role::Hr, &hr));
+    assert_eq!(
+        client.try_resume_employee_salary(&hr, &employee1),
+        Err(Ok(SalaryError::Unauthorized))
+    );
+
+    // The admin holds every role
+    client.resume_employee_salary(&admin, &employee1);
+    assert!(client.get_employee(&employee1).is_active);
+}
+
+#[test]
+fn test_only_admin_manages_roles() {
+    let env = Env::default();
+
+    let admin = Address::generate(&env);
+    let hr = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, _) = create_token_contract(&env, &token_admin);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&admin, &500, &token_client.address);
+
+    // HR cannot grant itself roles: the admin's signature is required
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &hr,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "grant_role",
+                args: (Role::Hr, &hr).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_grant_role(&Role::Hr, &hr);
+    assert!(result.is_err());
+    assert!(!client.has_role(&Role::Hr, &hr));
+}
+
+#[test]
+fn test_multisig_increment_change() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let signer1 = Address::generate(&env);
+    let signer2 = Address::generate(&env);
+    let signer3 = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, _) = create_token_contract(&env, &token_admin);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+
+    // Without signers the admin changes the increment directly
+    let action = ProposalAction::SetAnnualIncrement(800);
+    assert_eq!(
+        client.try_create_proposal(&admin, &action),
+        Err(Ok(SalaryError::InvalidSigners))
+    );
+    client.set_annual_increment(&600);
+    assert_eq!(client.get_annual_increment(), 600);
+
+    client.set_signers(&vec![&env, signer1.clone(), signer2.clone(), signer3.clone()], &2);
+    assert_eq!(client.get_signer_threshold(), 2);
+    assert_eq!(
+        client.try_set_annual_increment(&700),
+        Err(Ok(SalaryError::ApprovalRequired))
+    );
+
+    assert_eq!(
+        client.try_create_proposal(&stranger, &action),
+        Err(Ok(SalaryError::Unauthorized))
+    );
+    let id = client.create_proposal(&signer1, &action);
+    assert_eq!(client.get_proposal_status(&id), ProposalStatus::Pending);
+    assert_eq!(
+        client.try_execute_proposal(&id),
+        Err(Ok(SalaryError::NotEnoughApprovals))
+    );
+    assert_eq!(
+        client.try_approve_proposal(&signer1, &id),
+        Err(Ok(SalaryError::AlreadyApproved))
+    );
+    assert_eq!(
+        client.try_approve_proposal(&stranger, &id),
+        Err(Ok(SalaryError::NotSigner))
+    );
+
+    client.approve_proposal(&signer2, &id);
+    assert_eq!(client.get_proposal_status(&id), ProposalStatus::Approved);
+    assert_eq!(client.get_annual_increment(), 600);
+
+    client.execute_proposal(&id);
+    assert_last_event(&env, &contract_id, events::ProposalExecuted { id });
+    assert_eq!(client.get_annual_increment(), 800);
+    assert_eq!(client.get_proposal_status(&id), ProposalStatus::Executed);
+    assert_eq!(
+        client.get_proposal(&id).approvals,
+        vec![&env, signer1.clone(), signer2.clone()]
+    );
+    assert_eq!(
+        client.try_execute_proposal(&id),
+        Err(Ok(SalaryError::ProposalAlreadyExecuted))
+    );
+    assert_eq!(
+        client.try_get_proposal(&(id + 1)),
+        Err(Ok(SalaryError::ProposalNotFound))
+    );
+}
+
+#[test]
+fn test_multisig_proposal_expiry() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let signer1 = Address::generate(&env);
+    let signer2 = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, _) = create_token_contract(&env, &token_admin);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2024, 3, 1);
+    client.initialize(&admin, &500, &token_client.address);
+    client.set_signers(&vec![&env, signer1.clone(), signer2.clone()], &2);
+
+    // The admin may propose, but the proposal carries no approval of its own
+    let id = client.create_proposal(&admin, &ProposalAction::SetAnnualIncrement(300));
+    assert_eq!(client.get_proposal(&id).approvals.len(), 0);
+    client.approve_proposal(&signer1, &id);
+
+    set_date(&env, 2024, 3, 9);
+    assert_eq!(client.get_proposal_status(&id), ProposalStatus::Expired);
+    assert_eq!(
+        client.try_approve_proposal(&signer2, &id),
+        Err(Ok(SalaryError::ProposalExpired))
+    );
+    assert_eq!(
+        client.try_execute_proposal(&id),
+        Err(Ok(SalaryError::ProposalExpired))
+    );
+    assert_eq!(client.get_annual_increment(), 500);
+}
+
+#[test]
+fn test_multisig_signer_rotation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let signer1 = Address::generate(&env);
+    let signer2 = Address::generate(&env);
+    let signer3 = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, _) = create_token_contract(&env, &token_admin);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+
+    let signers = vec![&env, signer1.clone(), signer2.clone()];
+    assert_eq!(client.try_set_signers(&signers, &0), Err(Ok(SalaryError::InvalidSigners)));
+    assert_eq!(client.try_set_signers(&signers, &3), Err(Ok(SalaryError::InvalidSigners)));
+    assert_eq!(
+        client.try_set_signers(&vec![&env, signer1.clone(), signer1.clone()], &1),
+        Err(Ok(SalaryError::InvalidSigners))
+    );
+
+    client.set_signers(&signers, &1);
+    assert_eq!(
+        client.try_set_signers(&vec![&env, signer3.clone()], &1),
+        Err(Ok(SalaryError::ApprovalRequired))
+    );
+
+    // Rotating the signer set is itself a guarded change
+    let id = client.create_proposal(
+        &signer1,
+        &ProposalAction::SetSigners(vec![&env, signer2.clone(), signer3.clone()], 2),
+    );
+    client.execute_proposal(&id);
+    assert_eq!(client.get_signers(), vec![&env, signer2.clone(), signer3.clone()]);
+    assert_eq!(client.get_signer_threshold(), 2);
+
+    // Removed signers can no longer propose, and the new threshold applies
+    let action = ProposalAction::SetAnnualIncrement(900);
+    assert_eq!(
+        client.try_create_proposal(&signer1, &action),
+        Err(Ok(SalaryError::Unauthorized))
+    );
+    let id = client.create_proposal(&signer2, &action);
+    assert_eq!(
+        client.try_execute_proposal(&id),
+        Err(Ok(SalaryError::NotEnoughApprovals))
+    );
+    client.approve_proposal(&signer3, &id);
+    client.execute_proposal(&id);
+    assert_eq!(client.get_annual_increment(), 900);
+}
+
+#[test]
+fn test_withdraw_treasury_respects_reserve() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let treasurer = Address::generate(&env);
+    let hr = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+    client.grant_role(&Role::Treasurer, &treasurer);
+    client.grant_role(&Role::Hr, &hr);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+
+    client.add_employee(&admin, &alice, &String::from_str(&env, "Alice"), &1_200_000);
+    client.add_employee(&admin, &bob, &String::from_str(&env, "Bob"), &2_400_000);
+    client.pay_salary(&admin, &alice);
+    client.stop_employee_salary(&admin, &bob);
+
+    // Alice: 3 months ahead at 100,000. Bob: stopped the day he was hired, so owed nothing
+    client.set_reserve_months(&3);
+    assert_eq!(client.get_required_reserve(&token_client.address), 300_000);
+
+    assert_eq!(
+        client.try_withdraw_treasury(&hr, &token_client.address, &recipient, &100_000),
+        Err(Ok(SalaryError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_withdraw_treasury(&treasurer, &token_client.address, &recipient, &4_700_000),
+        Err(Ok(SalaryError::ReserveViolation))
+    );
+    assert_eq!(
+        client.try_withdraw_treasury(&treasurer, &token_client.address, &recipient, &0),
+        Err(Ok(SalaryError::InvalidAmount))
+    );
+
+    client.withdraw_treasury(&treasurer, &token_client.address, &recipient, &4_600_000);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::TreasuryWithdrawn {
+            token: token_client.address.clone(),
+            to: recipient.clone(),
+            amount: 4_600_000,
+            balance: 300_000,
+            reserve: 300_000,
+        },
+    );
+    assert_eq!(token_client.balance(&recipient), 4_600_000);
+    assert_eq!(client.get_treasury_balance(&token_client.address), 300_000);
+    assert_eq!(token_client.balance(&contract_id), 300_000);
+
+    // Resumed the same day, Bob is owed the whole month
+    client.resume_employee_salary(&admin, &bob);
+    client.pay_salary(&admin, &bob);
+    assert_eq!(token_client.balance(&bob), 200_000);
+}
+
+#[test]
+fn test_required_reserve_follows_payroll_changes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    let (other_client, _) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &100_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2025, 3, 10);
+    client.initialize(&admin, &500, &token_client.address);
+    client.add_accepted_token(&other_client.address);
+    client.fund_treasury(&funder, &token_client.address, &50_000_000);
+    client.set_reserve_months(&1);
+
+    client.add_employee(&admin, &alice, &String::from_str(&env, "Alice"), &1_200_000);
+    client.add_employee(&admin, &bob, &String::from_str(&env, "Bob"), &2_400_000);
+    assert_eq!(client.get_committed_payroll(&token_client.address), 3_600_000);
+    assert_eq!(client.get_required_reserve(&token_client.address), 300_000);
+
+    let reason = String::from_str(&env, "Promotion");
+    client.update_salary(&admin, &alice, &2_400_000, &2025, &3, &reason);
+    assert_eq!(client.get_committed_payroll(&token_client.address), 4_800_000);
+
+    client.stop_employee_salary(&admin, &bob);
+    assert_eq!(client.get_committed_payroll(&token_client.address), 2_400_000);
+    client.resume_employee_salary(&admin, &bob);
+    assert_eq!(client.get_committed_payroll(&token_client.address), 4_800_000);
+
+    client.set_payout_token(&admin, &bob, &other_client.address);
+    assert_eq!(client.get_committed_payroll(&token_client.address), 2_400_000);
+    assert_eq!(client.get_committed_payroll(&other_client.address), 2_400_000);
+
+    // A payment picks up the anniversary increment earned since
+    set_date(&env, 2026, 3, 10);
+    client.pay_salary(&admin, &alice);
+    assert_eq!(client.get_committed_payroll(&token_client.address), 2_520_000);
+
+    client.terminate_employee(&admin, &alice, &env.ledger().timestamp(), &reason);
+    assert_eq!(client.get_committed_payroll(&token_client.address), 0);
+
+    // Withdrawals no longer read every employee
+    for _ in 0..100 {
+        let employee = Address::generate(&env);
+        client.add_employee(&admin, &employee, &String::from_str(&env, "Staff"), &1_200_000);
+    }
+    assert_eq!(client.get_required_reserve(&token_client.address), 10_000_000);
+    env.cost_estimate().budget().reset_default();
+    client.withdraw_treasury(&admin, &token_client.address, &recipient, &1_000_000);
+    assert!(env.cost_estimate().budget().cpu_instruction_cost() < 2_000_000);
+}
+
+#[test]
+fn test_large_withdrawal_requires_multisig() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let signer1 = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.set_approval_amount(&1_000_000);
+    client.set_signers(&vec![&env, signer1.clone()], &1);
+
+    client.withdraw_treasury(&admin, &token_client.address, &recipient, &1_000_000);
+    assert_eq!(
+        client.try_withdraw_treasury(&admin, &token_client.address, &recipient, &2_000_000),
+        Err(Ok(SalaryError::ApprovalRequired))
+    );
+    assert_eq!(
+        client.try_set_reserve_months(&1),
+        Err(Ok(SalaryError::ApprovalRequired))
+    );
+    assert_eq!(
+        client.try_set_approval_amount(&5_000_000),
+        Err(Ok(SalaryError::ApprovalRequired))
+    );
+
+    let id = client.create_proposal(
+        &signer1,
+        &ProposalAction::WithdrawTreasury(token_client.address.clone(), recipient.clone(), 2_000_000),
+    );
+    client.execute_proposal(&id);
+    assert_eq!(token_client.balance(&recipient), 3_000_000);
+    assert_eq!(client.get_treasury_balance(&token_client.address), 2_000_000);
+}
+
+#[test]
+fn test_payroll_changes_require_multisig() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let hr = Address::generate(&env);
+    let signer1 = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let tax_authority = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, _) = create_token_contract(&env, &token_admin);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+    client.grant_role(&Role::Hr, &hr);
+    client.set_salary_approval_amount(&2_000_000);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::SalaryApprovalAmountChanged { amount: 2_000_000 },
+    );
+    client.set_signers(&vec![&env, signer1.clone()], &1);
+
+    // Hires up to the salary approval amount need no proposal
+    client.add_employee(&hr, &alice, &String::from_str(&env, "Alice"), &2_000_000);
+    assert_eq!(
+        client.try_add_employee(&hr, &bob, &String::from_str(&env, "Bob"), &2_000_001),
+        Err(Ok(SalaryError::ApprovalRequired))
+    );
+    assert_eq!(
+        client.try_set_salary_approval_amount(&1_000_000_000),
+        Err(Ok(SalaryError::ApprovalRequired))
+    );
+    assert_eq!(
+        client.try_set_severance_months(&100_000),
+        Err(Ok(SalaryError::ApprovalRequired))
+    );
+    let rules = vec![
+        &env,
+        DeductionRule {
+            name: String::from_str(&env, "Tax"),
+            kind: DeductionKind::Percentage(2_000),
+            destination: tax_authority.clone(),
+        },
+    ];
+    assert_eq!(
+        client.try_set_deductions(&rules),
+        Err(Ok(SalaryError::ApprovalRequired))
+    );
+
+    let id = client.create_proposal(
+        &signer1,
+        &ProposalAction::AddEmployee(bob.clone(), String::from_str(&env, "Bob"), 3_000_000),
+    );
+    client.execute_proposal(&id);
+    assert_eq!(client.get_employee(&bob).base_salary, 3_000_000);
+
+    let id = client.create_proposal(&signer1, &ProposalAction::SetSeveranceMonths(2));
+    client.execute_proposal(&id);
+    assert_eq!(client.get_severance_months(), 2);
+
+    // Deduction rules are still validated when the proposal executes
+    let invalid = vec![
+        &env,
+        DeductionRule {
+            name: String::from_str(&env, "Tax"),
+            kind: DeductionKind::Percentage(0),
+            destination: tax_authority,
+        },
+    ];
+    let id = client.create_proposal(&signer1, &ProposalAction::SetDeductions(invalid));
+    assert_eq!(client.try_execute_proposal(&id), Err(Ok(SalaryError::InvalidAmount)));
+    let id = client.create_proposal(&signer1, &ProposalAction::SetDeductions(rules.clone()));
+    client.execute_proposal(&id);
+    assert_eq!(client.get_deductions(), rules);
+    // Raises follow the same salary approval amount
+    let reason = String::from_str(&env, "Promotion");
+    client.update_salary(&hr, &alice, &1_500_000, &1970, &2, &reason);
+    assert_eq!(
+        client.try_update_salary(&hr, &alice, &1_000_000_000_000, &1970, &2, &reason),
+        Err(Ok(SalaryError::ApprovalRequired))
+    );
+    let id = client.create_proposal(
+        &signer1,
+        &ProposalAction::UpdateSalary(alice.clone(), 2_500_000, 1970, 3, reason.clone()),
+    );
+    client.execute_proposal(&id);
+    let history = client.get_compensation_history(&alice);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(1).unwrap().base_salary, 2_500_000);
+
+    // Increment overrides that can pay more than the global increment need a
+    // proposal; lower rates and clearing an override don't
+    let generous = IncrementPolicy {
+        rate: 10_000,
+        method: IncrementMethod::Compound,
+        cap: 0,
+        basis: IncrementBasis::Anniversary,
+    };
+    assert_eq!(
+        client.try_set_increment_policy(&hr, &alice, &Some(generous.clone())),
+        Err(Ok(SalaryError::ApprovalRequired))
+    );
+    assert_eq!(
+        client.try_set_increment_policy(
+            &hr,
+            &alice,
+            &Some(IncrementPolicy {
+                rate: 500,
+                basis: IncrementBasis::CalendarYear,
+                ..generous.clone()
+            })
+        ),
+        Err(Ok(SalaryError::ApprovalRequired))
+    );
+    let modest = IncrementPolicy {
+        rate: 300,
+        method: IncrementMethod::Simple,
+        cap: 3_000_000,
+        basis: IncrementBasis::Anniversary,
+    };
+    client.set_increment_policy(&hr, &alice, &Some(modest.clone()));
+    assert_eq!(client.get_increment_policy(&alice), modest);
+    client.set_increment_policy(&hr, &alice, &None);
+    assert_eq!(client.get_increment_policy(&alice).rate, 500);
+
+    let id = client.create_proposal(
+        &signer1,
+        &ProposalAction::SetIncrementPolicy(alice.clone(), generous.clone()),
+    );
+    client.execute_proposal(&id);
+    assert_eq!(client.get_increment_policy(&alice), generous);
+}
+
+#[test]
+fn test_reconcile_treasury_surplus() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let auditor = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+    client.grant_role(&Role::Auditor, &auditor);
+    client.fund_treasury(&funder, &token_client.address, &1_000_000);
+    assert_eq!(client.get_treasury_discrepancy(&token_client.address), 0);
+
+    // Someone transfers tokens straight to the contract
+    token_client.transfer(&funder, &contract_id, &250_000);
+    assert_eq!(client.get_treasury_discrepancy(&token_client.address), 250_000);
+    assert_eq!(
+        client.try_reconcile_treasury(&funder, &token_client.address),
+        Err(Ok(SalaryError::Unauthorized))
+    );
+    let (other_token, _) = create_token_contract(&env, &token_admin);
+    assert_eq!(
+        client.try_reconcile_treasury(&auditor, &other_token.address),
+        Err(Ok(SalaryError::TokenNotAccepted))
+    );
+
+    assert_eq!(client.reconcile_treasury(&auditor, &token_client.address), 250_000);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::TreasuryReconciled {
+            token: token_client.address.clone(),
+            tracked: 1_000_000,
+            actual: 1_250_000,
+            surplus: 250_000,
+            deficit: 0,
+        },
+    );
+    assert_eq!(client.get_treasury_balance(&token_client.address), 1_250_000);
+    assert_eq!(client.get_untracked_deposits(&token_client.address), 250_000);
+    assert_eq!(client.get_treasury_discrepancy(&token_client.address), 0);
+
+    assert_eq!(client.reconcile_treasury(&auditor, &token_client.address), 0);
+    assert_eq!(client.get_untracked_deposits(&token_client.address), 250_000);
+}
+
+#[test]
+fn test_reconcile_treasury_deficit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let auditor = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let sac = env.register_stellar_asset_contract_v2(token_admin.clone());
+    sac.issuer().set_flag(IssuerFlags::ClawbackEnabledFlag);
+    let token_client = token::Client::new(&env, &sac.address());
+    let token_admin_client = token::StellarAssetClient::new(&env, &sac.address());
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+    client.grant_role(&Role::Auditor, &auditor);
+    client.fund_treasury(&funder, &token_client.address, &1_000_000);
+
+    // Tokens leave the contract without going through the treasury
+    token_admin_client.clawback(&contract_id, &100_000);
+    assert_eq!(client.get_treasury_discrepancy(&token_client.address), -100_000);
+
+    assert_eq!(client.reconcile_treasury(&auditor, &token_client.address), -100_000);
+    assert_eq!(client.get_treasury_deficit(&token_client.address), 100_000);
+    assert_eq!(client.get_treasury_balance(&token_client.address), 1_000_000);
+    assert_eq!(client.get_untracked_deposits(&token_client.address), 0);
+
+    // Topping up clears the flag on the next reconciliation
+    token_client.transfer(&funder, &contract_id, &100_000);
+    assert_eq!(client.reconcile_treasury(&auditor, &token_client.address), 0);
+    assert_eq!(client.get_treasury_deficit(&token_client.address), 0);
+    env.as_contract(&contract_id, || {
+        assert!(!env
+            .storage()
+            .instance()
+            .has(&DataKey::TreasuryDeficit(token_client.address.clone())));
+    });
+}
+
+#[test]
+fn test_multi_token_payroll() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let employee2 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    let (other_client, other_admin_client) = create_token_contract(&env, &token_admin);
+    let (unlisted_client, _) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+    other_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+    assert_eq!(client.get_accepted_tokens(), vec![&env, token_client.address.clone()]);
+    assert_eq!(
+        client.try_fund_treasury(&funder, &other_client.address, &1_000_000),
+        Err(Ok(SalaryError::TokenNotAccepted))
+    );
+
+    client.add_accepted_token(&other_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.fund_treasury(&funder, &other_client.address, &200_000);
+
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
+    client.add_employee(&admin, &employee2, &String::from_str(&env, "Bob"), &2_400_000);
+    assert_eq!(client.get_employee(&employee1).payout_token, token_client.address);
+    assert_eq!(
+        client.try_set_payout_token(&admin, &employee2, &unlisted_client.address),
+        Err(Ok(SalaryError::TokenNotAccepted))
+    );
+    client.set_payout_token(&admin, &employee2, &other_client.address);
+
+    client.pay_salary(&admin, &employee1);
+    client.claim_salary(&employee2);
+    assert_eq!(token_client.balance(&employee1), 100_000);
+    assert_eq!(other_client.balance(&employee2), 200_000);
+    assert_eq!(token_client.balance(&employee2), 0);
+    assert_eq!(client.get_treasury_balance(&token_client.address), 4_900_000);
+    assert_eq!(client.get_treasury_balance(&other_client.address), 0);
+
+    // Each bucket only covers the employees paid in its token
+    advance_months(&env, 1);
+    client.pay_salary(&admin, &employee1);
+    assert_eq!(
+        client.try_pay_salary(&admin, &employee2),
+        Err(Ok(SalaryError::InsufficientTreasury))
+    );
+
+    // Delisting stops funding but not payouts from the remaining balance
+    client.fund_treasury(&funder, &other_client.address, &200_000);
+    client.remove_accepted_token(&other_client.address);
+    assert_eq!(
+        client.try_fund_treasury(&funder, &other_client.address, &200_000),
+        Err(Ok(SalaryError::TokenNotAccepted))
+    );
+    client.pay_salary(&admin, &employee2);
+    assert_eq!(other_client.balance(&employee2), 400_000);
+}
+
+#[test]
+fn test_sustainability_years_per_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let employee2 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+    let (other_client, other_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+    other_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &0, &token_client.address);
+    client.add_accepted_token(&other_client.address);
+    client.fund_treasury(&funder, &token_client.address, &10_000_000);
+    client.fund_treasury(&funder, &other_client.address, &3_000_000);
+
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_000_000);
+    client.add_employee(&admin, &employee2, &String::from_str(&env, "Bob"), &1_000_000);
+    assert_eq!(client.calculate_sustainability_years(), 5);
+
+    // The smaller bucket runs out first
+    client.set_payout_token(&admin, &employee2, &other_client.address);
+    assert_eq!(client.calculate_sustainability_years(), 3);
+}
+
+#[test]
+fn test_payout_split() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let savings = Address::generate(&env);
+    let family = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_012);
+
+    let share = |destination: &Address, basis_points: u32| PayoutShare {
+        destination: destination.clone(),
+        basis_points,
+    };
+
+    // Shares must be positive, distinct and add up to 100%
+    for split in [
+        vec![&env, share(&savings, 5_000)],
+        vec![&env, share(&savings, 10_000), share(&family, 0)],
+        vec![&env, share(&savings, 5_000), share(&savings, 5_000)],
+    ] {
+        assert_eq!(
+            client.try_set_payout_split(&employee1, &split),
+            Err(Ok(SalaryError::InvalidSplit))
+        );
+    }
+
+    let split = vec![
+        &env,
+        share(&employee1, 3_333),
+        share(&savings, 3_333),
+        share(&family, 3_334),
+    ];
+
+    // Only the employee can change where their salary goes
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "set_payout_split",
+                args: (&employee1, split.clone()).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_set_payout_split(&employee1, &split);
+    assert!(result.is_err());
+
+    client.set_payout_split(&employee1, &split);
+    assert_eq!(client.get_payout_split(&employee1), split);
+
+    // 100,001 per month: 33,330 + 33,340 routed, the rest (with dust) to the employee
+    client.pay_salary(&admin, &employee1);
+    assert_eq!(token_client.balance(&savings), 33_330);
+    assert_eq!(token_client.balance(&family), 33_340);
+    assert_eq!(token_client.balance(&employee1), 33_331);
+    assert_eq!(client.get_treasury_balance(&token_client.address), 4_899_999);
+
+    // Clearing the split pays the employee in full again
+    client.set_payout_split(&employee1, &vec![&env]);
+    advance_months(&env, 1);
+    client.claim_salary(&employee1);
+    assert_eq!(token_client.balance(&employee1), 133_332);
+    assert_eq!(token_client.balance(&savings), 33_330);
+}
+
+#[test]
+fn test_deductions_withheld_from_gross_pay() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let tax_authority = Address::generate(&env);
+    let pension_fund = Address::generate(&env);
+    let insurer = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
+
+    let rule = |name: &str, kind: DeductionKind, destination: &Address| DeductionRule {
+        name: String::from_str(&env, name),
+        kind,
+        destination: destination.clone(),
+    };
+    assert_eq!(
+        client.try_set_deductions(&vec![&env, rule("Tax", DeductionKind::Percentage(10_001), &tax_authority)]),
+        Err(Ok(SalaryError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_set_deductions(&vec![&env, rule("Insurance", DeductionKind::Fixed(0), &insurer)]),
+        Err(Ok(SalaryError::InvalidAmount))
+    );
+
+    let rules = vec![
+        &env,
+        rule("Tax", DeductionKind::Percentage(2_000), &tax_authority),
+        rule("Pension", DeductionKind::Percentage(500), &pension_fund),
+        rule("Insurance", DeductionKind::Fixed(3_000), &insurer),
+    ];
+    client.set_deductions(&rules);
+    assert_eq!(client.get_deductions(), rules);
+
+    // Two months at once: fixed deductions apply once per pay period
+    advance_months(&env, 1);
+    let payslip = client.pay_salary(&admin, &employee1);
+    assert_eq!(
+        payslip,
+        Payslip {
+            id: 1,
+            employee: employee1.clone(),
+            periods: 2,
+            period_start: calendar::timestamp_from_date(1970, 1, 1).unwrap(),
+            period_end: calendar::timestamp_from_date(1970, 3, 1).unwrap(),
+            gross: 200_000,
+            deductions: vec![
+                &env,
+                DeductionLine {
+                    name: String::from_str(&env, "Tax"),
+                    destination: tax_authority.clone(),
+                    amount: 40_000,
+                },
+                DeductionLine {
+                    name: String::from_str(&env, "Pension"),
+                    destination: pension_fund.clone(),
+                    amount: 10_000,
+                },
+                DeductionLine {
+                    name: String::from_str(&env, "Insurance"),
+                    destination: insurer.clone(),
+                    amount: 6_000,
+                },
+            ],
+            net: 144_000,
+            token: token_client.address.clone(),
+            paid_at: calendar::timestamp_from_date(1970, 2, 1).unwrap(),
+            paid_by: admin.clone(),
+            self_claim: false,
+        }
+    );
+    assert_eq!(token_client.balance(&employee1), 144_000);
+    assert_eq!(token_client.balance(&tax_authority), 40_000);
+    assert_eq!(token_client.balance(&pension_fund), 10_000);
+    assert_eq!(token_client.balance(&insurer), 6_000);
+    assert_eq!(client.get_treasury_balance(&token_client.address), 4_800_000);
+
+    // A rule never takes more than the pay left after earlier rules
+    client.set_deductions(&vec![
+        &env,
+        rule("Tax", DeductionKind::Percentage(9_000), &tax_authority),
+        rule("Insurance", DeductionKind::Fixed(30_000), &insurer),
+    ]);
+    advance_months(&env, 1);
+    let payslip = client.claim_salary(&employee1);
+    assert_eq!(payslip.deductions.get(0).unwrap().amount, 90_000);
+    assert_eq!(payslip.deductions.get(1).unwrap().amount, 10_000);
+    assert_eq!(payslip.net, 0);
+    assert_eq!(token_client.balance(&employee1), 144_000);
+}
+
+#[test]
+fn test_streaming_withdrawal_withholds_percentage_deductions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let tax_authority = Address::generate(&env);
+    let insurer = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
+    client.enable_streaming(&admin, &employee1);
+    client.set_deductions(&vec![
+        &env,
+        DeductionRule {
+            name: String::from_str(&env, "Tax"),
+            kind: DeductionKind::Percentage(2_500),
+            destination: tax_authority.clone(),
+        },
+        DeductionRule {
+            name: String::from_str(&env, "Insurance"),
+            kind: DeductionKind::Fixed(3_000),
+            destination: insurer.clone(),
+        },
+    ]);
+
+    advance_months(&env, 1);
+    let payslip = client.withdraw_accrued(&employee1, &80_000);
+    assert_eq!(payslip.periods, 0);
+    assert_eq!(payslip.gross, 80_000);
+    assert_eq!(payslip.net, 60_000);
+    assert_eq!(token_client.balance(&employee1), 60_000);
+    assert_eq!(token_client.balance(&tax_authority), 20_000);
+    assert_eq!(token_client.balance(&insurer), 0);
+}
+
+#[test]
+fn test_payslip_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let employee2 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2024, 3, 10);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.grant_role(&Role::PayrollOperator, &operator);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
+    client.add_employee(&admin, &employee2, &String::from_str(&env, "Bob"), &2_400_000);
+
+    client.pay_salary(&operator, &employee1);
+    client.pay_salary(&operator, &employee2);
+    advance_months(&env, 2);
+    client.claim_salary(&employee1);
+
+    let first = client.get_payslip(&1);
+    assert_eq!(first.employee, employee1);
+    assert_eq!(first.periods, 1);
+    assert_eq!(first.period_start, calendar::timestamp_from_date(2024, 3, 1).unwrap());
+    assert_eq!(first.period_end, calendar::timestamp_from_date(2024, 4, 1).unwrap());
+    assert_eq!(first.gross, 70_967);
+    assert_eq!(first.net, 70_967);
+    assert_eq!(first.token, token_client.address);
+    assert_eq!(first.paid_at, calendar::timestamp_from_date(2024, 3, 10).unwrap());
+    assert_eq!(first.paid_by, operator);
+    assert!(!first.self_claim);
+
+    let claimed = client.get_payslip(&3);
+    assert_eq!(claimed.periods, 2);
+    assert_eq!(claimed.period_start, calendar::timestamp_from_date(2024, 4, 1).unwrap());
+    assert_eq!(claimed.period_end, calendar::timestamp_from_date(2024, 6, 1).unwrap());
+    assert_eq!(claimed.gross, 200_000);
+    assert_eq!(claimed.paid_by, employee1);
+    assert!(claimed.self_claim);
+
+    assert_eq!(
+        client.get_payslips(&employee1, &0, &10),
+        vec![&env, first.clone(), claimed.clone()]
+    );
+    assert_eq!(client.get_payslips(&employee1, &1, &10), vec![&env, claimed]);
+    assert_eq!(client.get_payslips(&employee2, &0, &1).get(0).unwrap().id, 2);
+    assert_eq!(client.get_payslips(&employee2, &5, &1), vec![&env]);
+    assert_eq!(
+        client.try_get_payslips(&employee1, &0, &0),
+        Err(Ok(SalaryError::InvalidLimit))
+    );
+    assert_eq!(client.try_get_payslip(&4), Err(Ok(SalaryError::PayslipNotFound)));
+}
+
+#[test]
+fn test_employee_lifecycle_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::Initialized {
+            admin: admin.clone(),
+            token: token_client.address.clone(),
+            annual_increment_bps: 500,
+        },
+    );
+
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::TreasuryFunded {
+            token: token_client.address.clone(),
+            from: funder.clone(),
+            amount: 5_000_000,
+            balance: 5_000_000,
+        },
+    );
+
+    let name = String::from_str(&env, "Alice");
+    client.add_employee(&admin, &employee1, &name, &1_200_000);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::EmployeeAdded {
+            employee: employee1.clone(),
+            name,
+            base_salary: 1_200_000,
+            payout_token: token_client.address.clone(),
+        },
+    );
+
+    client.pay_salary(&admin, &employee1);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::SalaryPaid {
+            employee: employee1.clone(),
+            payslip_id: 1,
+            token: token_client.address.clone(),
+            periods: 1,
+            gross: 100_000,
+            net: 100_000,
+            paid_by: admin.clone(),
+            self_claim: false,
+        },
+    );
+
+    client.stop_employee_salary(&admin, &employee1);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::SalaryStopped {
+            employee: employee1.clone(),
+        },
+    );
+
+    advance_months(&env, 1);
+    client.resume_employee_salary(&admin, &employee1);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::SalaryResumed {
+            employee: employee1.clone(),
+        },
+    );
+
+    client.claim_salary(&employee1);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::SalaryPaid {
+            employee: employee1.clone(),
+            payslip_id: 2,
+            token: token_client.address.clone(),
+            periods: 1,
+            gross: 100_000,
+            net: 100_000,
+            paid_by: employee1.clone(),
+            self_claim: true,
+        },
+    );
+
+    client.enable_streaming(&admin, &employee1);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::StreamingEnabled {
+            employee: employee1.clone(),
+            accrual_checkpoint: calendar::timestamp_from_date(1970, 3, 1).unwrap(),
+        },
+    );
+}
+
+#[test]
+fn test_config_change_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let savings = Address::generate(&env);
+    let signer1 = Address::generate(&env);
+    let tax_authority = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, _) = create_token_contract(&env, &token_admin);
+    let (other_client, _) = create_token_contract(&env, &token_admin);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
+
+    client.set_annual_increment(&700);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::AnnualIncrementChanged {
+            annual_increment_bps: 700,
+        },
+    );
+
+    client.set_max_arrears_periods(&6);
+    assert_last_event(&env, &contract_id, events::MaxArrearsPeriodsChanged { periods: 6 });
+
+    client.set_default_pay_frequency(&PayFrequency::BiWeekly);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::DefaultPayFrequencyChanged {
+            frequency: PayFrequency::BiWeekly,
+        },
+    );
+
+    client.set_employee_pay_frequency(&admin, &employee1, &Some(PayFrequency::Weekly));
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::PayFrequencyChanged {
+            employee: employee1.clone(),
+            frequency: PayFrequency::Weekly,
+        },
+    );
+    client.set_employee_pay_frequency(&admin, &employee1, &None);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::PayFrequencyChanged {
+            employee: employee1.clone(),
+            frequency: PayFrequency::BiWeekly,
+        },
+    );
+
+    client.add_accepted_token(&other_client.address);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::AcceptedTokenAdded {
+            token: other_client.address.clone(),
+        },
+    );
+
+    client.set_payout_token(&admin, &employee1, &other_client.address);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::PayoutTokenChanged {
+            employee: employee1.clone(),
+            token: other_client.address.clone(),
+        },
+    );
+
+    client.remove_accepted_token(&other_client.address);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::AcceptedTokenRemoved {
+            token: other_client.address.clone(),
+        },
+    );
+
+    let split = vec![
+        &env,
+        PayoutShare {
+            destination: savings.clone(),
+            basis_points: 10_000,
+        },
+    ];
+    client.set_payout_split(&employee1, &split);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::PayoutSplitChanged {
+            employee: employee1.clone(),
+            split,
+        },
+    );
+
+    let rules = vec![
+        &env,
+        DeductionRule {
+            name: String::from_str(&env, "Tax"),
+            kind: DeductionKind::Percentage(2_000),
+            destination: tax_authority,
+        },
+    ];
+    client.set_deductions(&rules);
+    assert_last_event(&env, &contract_id, events::DeductionsChanged { rules });
+
+    client.set_approval_amount(&1_000);
+    assert_last_event(&env, &contract_id, events::ApprovalAmountChanged { amount: 1_000 });
+
+    client.set_reserve_months(&2);
+    assert_last_event(&env, &contract_id, events::ReserveMonthsChanged { months: 2 });
+
+    let signers = vec![&env, signer1.clone()];
+    client.set_signers(&signers, &1);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::SignersChanged {
+            signers,
+            threshold: 1,
+        },
+    );
+
+    // Changes made through a proposal publish the same events
+    let id = client.create_proposal(&signer1, &ProposalAction::SetReserveMonths(3));
+    client.execute_proposal(&id);
+    let changed = events::ReserveMonthsChanged { months: 3 };
+    let all = env.events().all();
+    assert_eq!(
+        all.slice(all.len() - 2..all.len() - 1),
+        vec![&env, (contract_id.clone(), changed.topics(&env), changed.data(&env))]
+    );
+    assert_last_event(&env, &contract_id, events::ProposalExecuted { id });
+}
+
+#[test]
+fn test_employee_ttl_extension() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let employee2 = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, _) = create_token_contract(&env, &token_admin);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    client.initialize(&admin, &500, &token_client.address);
+    assert_eq!(
+        client.try_set_ttl_config(&10_000, &5_000),
+        Err(Ok(SalaryError::InvalidLimit))
+    );
+    assert_eq!(
+        client.try_set_ttl_config(&5_000, &u32::MAX),
+        Err(Ok(SalaryError::InvalidLimit))
+    );
+    client.set_ttl_config(&5_000, &10_000);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::TtlConfigChanged {
+            threshold: 5_000,
+            extend_to: 10_000,
+        },
+    );
+    assert_eq!(
+        client.get_ttl_config(),
+        TtlConfig {
+            threshold: 5_000,
+            extend_to: 10_000,
+        }
+    );
+
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
+    client.add_employee(&admin, &employee2, &String::from_str(&env, "Bob"), &1_200_000);
+    assert_eq!(client.count_employees(&0, &u32::MAX, &None).matching, 2);
+
+    let ttl = |key: DataKey| {
+        env.as_contract(&contract_id, || env.storage().persistent().get_ttl(&key))
+    };
+    assert_eq!(ttl(DataKey::Employee(employee1.clone())), 10_000);
+
+    // Reading a record extends it once it falls below the threshold
+    let sequence = env.ledger().sequence();
+    env.ledger().set_sequence_number(sequence + 4_000);
+    client.get_employee(&employee1);
+    assert_eq!(ttl(DataKey::Employee(employee1.clone())), 6_000);
+    env.ledger().set_sequence_number(sequence + 5_500);
+    client.get_employee(&employee1);
+    assert_eq!(ttl(DataKey::Employee(employee1.clone())), 10_000);
+    assert_eq!(ttl(DataKey::Employee(employee2.clone())), 4_500);
+
+    // bump_all keeps every record alive, batch by batch
+    assert_eq!(client.bump_all(&0, &1), Some(1));
+    assert_eq!(ttl(DataKey::EmployeeAt(0)), 10_000);
+    assert_eq!(ttl(DataKey::Employee(employee2.clone())), 4_500);
+    assert_eq!(client.bump_all(&1, &1), None);
+    assert_eq!(ttl(DataKey::EmployeeAt(1)), 10_000);
+    assert_eq!(ttl(DataKey::Employee(employee2.clone())), 10_000);
+}
+
+#[test]
+fn test_payslip_termination_and_proposal_ttl_extension() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let signer1 = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2025, 3, 1);
+    client.initialize(&admin, &500, &token_client.address);
+    client.set_ttl_config(&5_000, &10_000);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &alice, &String::from_str(&env, "Alice"), &1_200_000);
+    client.add_employee(&admin, &bob, &String::from_str(&env, "Bob"), &1_200_000);
+    let alice_payslip = client.pay_salary(&admin, &alice).id;
+    let bob_payslip = client.pay_salary(&admin, &bob).id;
+    client.terminate_employee(&admin, &bob, &env.ledger().timestamp(), &String::from_str(&env, "Resigned"));
+    client.set_signers(&vec![&env, signer1.clone()], &1);
+    let proposal = client.create_proposal(&signer1, &ProposalAction::SetReserveMonths(1));
+
+    let ttl = |key: DataKey| {
+        env.as_contract(&contract_id, || env.storage().persistent().get_ttl(&key))
+    };
+    let records = [
+        DataKey::Payslip(alice_payslip),
+        DataKey::Payslip(bob_payslip),
+        DataKey::EmployeePayslips(bob.clone()),
+        DataKey::Termination(bob.clone()),
+        DataKey::TerminatedAt(0),
+        DataKey::Proposal(proposal),
+    ];
+
+    // Reading a record extends it once it falls below the threshold
+    let sequence = env.ledger().sequence();
+    env.ledger().set_sequence_number(sequence + 5_500);
+    assert_eq!(ttl(DataKey::Payslip(alice_payslip)), 4_500);
+    client.get_payslip(&alice_payslip);
+    client.get_payslips(&bob, &0, &10);
+    client.get_termination(&bob);
+    client.get_proposal(&proposal);
+    for key in records.clone() {
+        let expected = if matches!(key, DataKey::TerminatedAt(_)) { 4_500 } else { 10_000 };
+        assert_eq!(ttl(key), expected);
+    }
+
+    // bump_all reaches them through the list, terminated employees and proposals
+    env.ledger().set_sequence_number(sequence + 11_000);
+    assert_eq!(client.bump_all(&0, &1), Some(1));
+    assert_eq!(ttl(DataKey::Payslip(alice_payslip)), 10_000);
+    assert_eq!(ttl(DataKey::Termination(bob.clone())), 4_500);
+    assert_eq!(client.bump_all(&1, &2), None);
+    for key in records {
+        assert_eq!(ttl(key), 10_000);
+    }
+}
+
+#[test]
+fn test_migrate_employees_from_instance_storage() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let signer1 = Address::generate(&env);
+    let newcomer = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    // A deployment of the original contract: one treasury balance, a 5%
+    // increment, and employee records in instance storage
+    set_date(&env, 2024, 3, 15);
+    token_admin_client.mint(&contract_id, &5_000_000);
+    let employees = vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    // (start year, start month, last paid year, last paid month)
+    let histories = [(2024, 1, 2024, 2), (2024, 2, 2024, 0), (2023, 12, 2024, 1)];
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        storage.set(&DataKey::Admin, &admin);
+        storage.set(&DataKey::AnnualIncrement, &5u32);
+        storage.set(&DataKey::TokenAddress, &token_client.address);
+        storage.set(&LegacyDataKey::TreasuryBalance, &5_000_000i128);
+        storage.set(&DataKey::EmployeeList, &employees);
+        for (employee, (start_year, start_month, paid_year, paid_month)) in
+            employees.iter().zip(histories)
+        {
+            storage.set(
+                &DataKey::Employee(employee.clone()),
+                &LegacyEmployee {
+                    address: employee.clone(),
+                    name: String::from_str(&env, "Staff"),
+                    base_salary: 1_200_000,
+                    start_year,
+                    start_month,
+                    last_payment_year: paid_year,
+                    last_payment_month: paid_month,
+                    is_active: true,
+                },
+            );
+        }
+    });
+    assert_eq!(client.count_employees(&0, &u32::MAX, &None).matching, 0);
+
+    // Hires would take list positions the migration is about to fill
+    assert_eq!(
+        client.try_add_employee(&admin, &newcomer, &String::from_str(&env, "New"), &1_200_000),
+        Err(Ok(SalaryError::MigrationPending))
+    );
+
+    assert_eq!(client.migrate_employees(&0, &2), Some(2));
+    assert_eq!(client.count_employees(&0, &u32::MAX, &None).matching, 2);
+    assert_eq!(client.get_annual_increment(), 500);
+    assert_eq!(client.get_treasury_balance(&token_client.address), 5_000_000);
+    assert_eq!(client.get_accepted_tokens(), vec![&env, token_client.address.clone()]);
+    assert_eq!(client.migrate_employees(&2, &2), None);
+    assert_eq!(client.get_all_employees(), employees);
+    assert_eq!(client.migrate_employees(&0, &2), None);
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        assert!(!storage.has(&DataKey::EmployeeList));
+        assert!(!storage.has(&LegacyDataKey::TreasuryBalance));
+        assert!(!storage.has(&DataKey::Employee(employees.get(0).unwrap())));
+    });
+
+    let first = client.get_employee(&employees.get(0).unwrap());
+    assert_eq!(first.start_timestamp, calendar::timestamp_from_date(2024, 1, 1).unwrap());
+    assert_eq!(first.paid_through, calendar::timestamp_from_date(2024, 3, 1).unwrap());
+    assert_eq!(first.payout_token, token_client.address);
+    assert_eq!(client.get_employee(&employees.get(1).unwrap()).paid_through, 0);
+
+    // Each is owed the months after the last one they were paid for
+    let run = client.run_payroll(&admin, &0, &10);
+    assert_eq!(run.outcomes.len(), 3);
+    assert_eq!(token_client.balance(&employees.get(0).unwrap()), 100_000);
+    assert_eq!(token_client.balance(&employees.get(1).unwrap()), 200_000);
+    assert_eq!(token_client.balance(&employees.get(2).unwrap()), 200_000);
+
+    client.add_employee(&admin, &newcomer, &String::from_str(&env, "New"), &1_200_000);
+    assert_eq!(client.count_employees(&0, &u32::MAX, &None).matching, 4);
+
+    // Replacing the code is a guarded change
+    client.set_signers(&vec![&env, signer1.clone()], &1);
+    assert_eq!(
+        client.try_upgrade(&BytesN::from_array(&env, &[0; 32])),
+        Err(Ok(SalaryError::ApprovalRequired))
+    );
+}
+
+#[test]
+fn test_migration_keeps_increment_set_after_upgrade() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, _) = create_token_contract(&env, &token_admin);
+    let (other_client, _) = create_token_contract(&env, &token_admin);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2024, 3, 15);
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        storage.set(&DataKey::Admin, &admin);
+        storage.set(&DataKey::AnnualIncrement, &5u32);
+        storage.set(&DataKey::TokenAddress, &token_client.address);
+        storage.set(&LegacyDataKey::TreasuryBalance, &5_000_000i128);
+        storage.set(&DataKey::EmployeeList, &vec![&env, employee.clone()]);
+        storage.set(
+            &DataKey::Employee(employee.clone()),
+            &LegacyEmployee {
+                address: employee.clone(),
+                name: String::from_str(&env, "Staff"),
+                base_salary: 1_200_000,
+                start_year: 2024,
+                start_month: 1,
+                last_payment_year: 2024,
+                last_payment_month: 0,
+                is_active: true,
+            },
+        );
+    });
+
+    // Set in basis points before migrating, the rate is kept as it is
+    client.add_accepted_token(&other_client.address);
+    client.set_annual_increment(&700);
+    assert_eq!(client.migrate_employees(&0, &10), None);
+    assert_eq!(client.get_annual_increment(), 700);
+    assert_eq!(client.get_treasury_balance(&token_client.address), 5_000_000);
+    assert_eq!(
+        client.get_accepted_tokens(),
+        vec![&env, other_client.address.clone(), token_client.address.clone()]
+    );
+
+    client.set_annual_increment(&800);
+    assert_eq!(client.get_annual_increment(), 800);
+}
+
+#[test]
+fn test_list_employees_with_filters() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, _) = create_token_contract(&env, &token_admin);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    let engineering = String::from_str(&env, "Engineering");
+    let sales = String::from_str(&env, "Sales");
+    let anyone = String::from_str(&env, "");
+
+    set_date(&env, 2022, 6, 1);
+    client.initialize(&admin, &500, &token_client.address);
+
+    // (hire year, department, active)
+    let roster = [
+        (2022, &engineering, true),
+        (2022, &sales, false),
+        (2023, &engineering, false),
+        (2024, &engineering, true),
+        (2024, &sales, true),
+    ];
+    let mut addresses = vec![&env];
+    for (year, department, active) in roster {
+        set_date(&env, year, 6, 1);
+        let employee = Address::generate(&env);
+        client.add_employee(&admin, &employee, &String::from_str(&env, "Staff"), &1_200_000);
+        client.set_employee_department(&admin, &employee, department);
+        if !active {
+            client.stop_employee_salary(&admin, &employee);
+        }
+        addresses.push_back(employee);
+    }
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::DepartmentChanged {
+            employee: addresses.get(4).unwrap(),
+            department: sales.clone(),
+        },
+    );
+
+    let addresses_of = |page: &EmployeePage| {
+        let mut result = vec![&env];
+        for employee in page.employees.iter() {
+            result.push_back(employee.address);
+        }
+        result
+    };
+    let filter = |status: StatusFilter, department: &String, from: u64, to: u64| {
+        Some(EmployeeFilter {
+            status,
+            department: department.clone(),
+            hired_from_year: from,
+            hired_to_year: to,
+        })
+    };
+
+    // Unfiltered pages walk the whole roster
+    let page = client.list_employees(&0, &2, &None);
+    assert_eq!(page.employees.get(0).unwrap(), client.get_employee(&addresses.get(0).unwrap()));
+    assert_eq!(addresses_of(&page), addresses.slice(0..2));
+    assert_eq!(page.next_cursor, Some(2));
+    let page = client.list_employees(&4, &2, &None);
+    assert_eq!(addresses_of(&page), addresses.slice(4..5));
+    assert_eq!(page.next_cursor, None);
+
+    // Filtered pages skip non-matching employees, scanning `limit` positions
+    let active_engineers = filter(StatusFilter::Active, &engineering, 0, 0);
+    let page = client.list_employees(&0, &2, &active_engineers);
+    assert_eq!(addresses_of(&page), vec![&env, addresses.get(0).unwrap()]);
+    assert_eq!(page.next_cursor, Some(2));
+    let page = client.list_employees(&2, &2, &active_engineers);
+    assert_eq!(addresses_of(&page), vec![&env, addresses.get(3).unwrap()]);
+    assert_eq!(page.next_cursor, Some(4));
+    let page = client.list_employees(&4, &2, &active_engineers);
+    assert_eq!(page.employees.len(), 0);
+    assert_eq!(page.next_cursor, None);
+
+    let count = |filter: &Option<EmployeeFilter>| {
+        let mut matching = 0;
+        let mut cursor = Some(0);
+        while let Some(start) = cursor {
+            let tally = client.count_employees(&start, &2, filter);
+            matching += tally.matching;
+            cursor = tally.next_cursor;
+        }
+        matching
+    };
+    assert_eq!(
+        client.count_employees(&1, &2, &None),
+        EmployeeTally {
+            matching: 4,
+            next_cursor: None,
+        }
+    );
+    assert_eq!(
+        client.count_employees(&0, &2, &active_engineers),
+        EmployeeTally {
+            matching: 1,
+            next_cursor: Some(2),
+        }
+    );
+    assert_eq!(count(&active_engineers), 2);
+    assert_eq!(count(&filter(StatusFilter::Inactive, &anyone, 0, 0)), 2);
+    assert_eq!(count(&filter(StatusFilter::Any, &sales, 0, 0)), 2);
+    assert_eq!(count(&filter(StatusFilter::Any, &anyone, 2023, 0)), 3);
+    assert_eq!(count(&filter(StatusFilter::Any, &anyone, 2022, 2023)), 3);
+    assert_eq!(count(&filter(StatusFilter::Active, &sales, 2024, 2024)), 1);
+
+    assert_eq!(
+        client.try_list_employees(&0, &0, &None),
+        Err(Ok(SalaryError::InvalidLimit))
+    );
+    assert_eq!(
+        client.try_count_employees(&0, &0, &active_engineers),
+        Err(Ok(SalaryError::InvalidLimit))
+    );
+}
+
+#[test]
+fn test_terminate_employee_with_final_settlement() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let employee2 = Address::generate(&env);
+    let employee3 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2024, 1, 1);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &300_000);
+    client.set_severance_months(&2);
+    for employee in [&employee1, &employee2, &employee3] {
+        client.add_employee(&admin, employee, &String::from_str(&env, "Staff"), &1_200_000);
+    }
+    client.pay_salary(&admin, &employee1);
+
+    set_date(&env, 2024, 3, 20);
+    let reason = String::from_str(&env, "Resigned");
+    assert_eq!(
+        client.try_terminate_employee(
+            &admin,
+            &employee1,
+            &calendar::timestamp_from_date(2024, 3, 21).unwrap(),
+            &reason
+        ),
+        Err(Ok(SalaryError::InvalidTerminationDate))
+    );
+    assert_eq!(
+        client.try_terminate_employee(
+            &admin,
+            &employee1,
+            &calendar::timestamp_from_date(2023, 12, 31).unwrap(),
+            &reason
+        ),
+        Err(Ok(SalaryError::InvalidTerminationDate))
+    );
+
+    // February in full, 15 of March's 31 days, and two months' severance
+    let termination_date = calendar::timestamp_from_date(2024, 3, 16).unwrap();
+    assert_eq!(
+        client.try_terminate_employee(&admin, &employee1, &termination_date, &reason),
+        Err(Ok(SalaryError::InsufficientTreasury))
+    );
+    client.fund_treasury(&funder, &token_client.address, &200_000);
+
+    let termination = client.terminate_employee(&admin, &employee1, &termination_date, &reason);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::EmployeeTerminated {
+            employee: employee1.clone(),
+            termination_date,
+            reason: reason.clone(),
+            final_payslip: termination.final_payslip,
+            gross: 348_387,
+        },
+    );
+    assert_eq!(termination.termination_date, termination_date);
+    assert_eq!(termination.reason, reason);
+    assert_eq!(termination.terminated_at, calendar::timestamp_from_date(2024, 3, 20).unwrap());
+    assert!(!termination.employee.is_active);
+    assert_eq!(client.get_termination(&employee1), termination);
+
+    let payslip = client.get_payslip(&termination.final_payslip);
+    assert_eq!(payslip.periods, 2);
+    assert_eq!(payslip.period_start, calendar::timestamp_from_date(2024, 2, 1).unwrap());
+    assert_eq!(payslip.period_end, termination_date);
+    assert_eq!(payslip.gross, 100_000 + 48_387 + 200_000);
+    assert_eq!(token_client.balance(&employee1), 100_000 + 348_387);
+    assert_eq!(client.get_treasury_balance(&token_client.address), 51_613);
+
+    // The last employee takes the vacated position
+    assert_eq!(client.count_employees(&0, &u32::MAX, &None).matching, 2);
+    assert_eq!(
+        client.get_all_employees(),
+        vec![&env, employee3.clone(), employee2.clone()]
+    );
+
+    assert_eq!(
+        client.try_get_employee(&employee1),
+        Err(Ok(SalaryError::EmployeeTerminated))
+    );
+    assert_eq!(
+        client.try_claim_salary(&employee1),
+        Err(Ok(SalaryError::EmployeeTerminated))
+    );
+    assert_eq!(
+        client.try_add_employee(&admin, &employee1, &String::from_str(&env, "Staff"), &1_200_000),
+        Err(Ok(SalaryError::EmployeeTerminated))
+    );
+    assert_eq!(
+        client.try_get_termination(&employee2),
+        Err(Ok(SalaryError::EmployeeNotFound))
+    );
+}
+
+#[test]
+fn test_terminate_streaming_employee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2023, 1, 1);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &3_650_000);
+    client.enable_streaming(&admin, &employee1);
+
+    // Accrual stops at the termination date, not when HR processes it
+    set_date(&env, 2023, 1, 20);
+    let termination = client.terminate_employee(
+        &admin,
+        &employee1,
+        &calendar::timestamp_from_date(2023, 1, 11).unwrap(),
+        &String::from_str(&env, "Contract ended"),
+    );
+    assert_eq!(client.get_payslip(&termination.final_payslip).gross, 100_000);
+    assert_eq!(token_client.balance(&employee1), 100_000);
+    assert_eq!(client.count_employees(&0, &u32::MAX, &None).matching, 0);
+    assert_eq!(client.calculate_sustainability_years(), 0);
+}
+
+#[test]
+fn test_terminate_streaming_employee_settled_past_termination() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let employee2 = Address::generate(&env);
+    let employee3 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    // 10,000 a day
+    set_date(&env, 2023, 1, 1);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    for employee in [&employee1, &employee2, &employee3] {
+        client.add_employee(&admin, employee, &String::from_str(&env, "Staff"), &3_650_000);
+        client.enable_streaming(&admin, employee);
+    }
+
+    set_date(&env, 2023, 1, 21);
+    client.stop_employee_salary(&admin, &employee1);
+    client.stop_employee_salary(&admin, &employee2);
+    client.withdraw_accrued(&employee3, &200_000);
+    set_date(&env, 2023, 1, 26);
+    client.resume_employee_salary(&admin, &employee2);
+    set_date(&env, 2023, 1, 31);
+    client.stop_employee_salary(&admin, &employee2);
+    assert_eq!(client.get_accrued(&employee2), 250_000);
+
+    // Stopped on Jan 21st, but employment ended on Jan 11th
+    let reason = String::from_str(&env, "Contract ended");
+    let termination = client.terminate_employee(
+        &admin,
+        &employee1,
+        &calendar::timestamp_from_date(2023, 1, 11).unwrap(),
+        &reason,
+    );
+    assert_eq!(client.get_payslip(&termination.final_payslip).gross, 100_000);
+    assert_eq!(token_client.balance(&employee1), 100_000);
+
+    // Jan 16-21 and Jan 26-31 accrued after the end; Jan 21-26 never accrued
+    client.terminate_employee(
+        &admin,
+        &employee2,
+        &calendar::timestamp_from_date(2023, 1, 16).unwrap(),
+        &reason,
+    );
+    assert_eq!(token_client.balance(&employee2), 150_000);
+
+    // Withdrawals already covered more than was owed
+    let termination = client.terminate_employee(
+        &admin,
+        &employee3,
+        &calendar::timestamp_from_date(2023, 1, 11).unwrap(),
+        &reason,
+    );
+    assert_eq!(termination.final_payslip, 0);
+    assert_eq!(token_client.balance(&employee3), 200_000);
+}
+
+#[test]
+fn test_prorated_first_and_last_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let employee2 = Address::generate(&env);
+    let employee3 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2024, 4, 1);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee3, &String::from_str(&env, "Carol"), &1_200_000);
+
+    // Hired partway through the day on April 11th: 20 of April's 30 days
+    env.ledger()
+        .set_timestamp(calendar::timestamp_from_date(2024, 4, 11).unwrap() + 9 * 60 * 60);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
+    client.add_employee(&admin, &employee2, &String::from_str(&env, "Bob"), &1_200_000);
+    assert_eq!(
+        client.get_arrears(&employee1),
+        Arrears {
+            periods: 1,
+            amount: 66_666,
+        }
+    );
+    client.claim_salary(&employee1);
+    assert_eq!(token_client.balance(&employee1), 66_666);
+
+    // Later periods are paid in full
+    set_date(&env, 2024, 5, 31);
+    client.claim_salary(&employee1);
+    assert_eq!(token_client.balance(&employee1), 166_666);
+
+    // Bob leaves before the end of his first month, Carol partway through May
+    set_date(&env, 2024, 6, 3);
+    let termination = client.terminate_employee(
+        &admin,
+        &employee2,
+        &calendar::timestamp_from_date(2024, 4, 21).unwrap(),
+        &String::from_str(&env, "Probation"),
+    );
+    assert_eq!(client.get_payslip(&termination.final_payslip).gross, 33_333);
+
+    let termination = client.terminate_employee(
+        &admin,
+        &employee3,
+        &calendar::timestamp_from_date(2024, 5, 11).unwrap(),
+        &String::from_str(&env, "Relocated"),
+    );
+    let payslip = client.get_payslip(&termination.final_payslip);
+    assert_eq!(payslip.periods, 2);
+    assert_eq!(payslip.gross, 100_000 + 32_258);
+}
+
+#[test]
+fn test_salary_changes_with_effective_dates() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let employee2 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2024, 1, 1);
+    client.initialize(&admin, &500, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
+    client.add_employee(&admin, &employee2, &String::from_str(&env, "Bob"), &1_200_000);
+    client.enable_streaming(&admin, &employee2);
+
+    set_date(&env, 2024, 1, 31);
+    client.claim_salary(&employee1);
+
+    // Schedule a promotion, then a market adjustment that takes effect before it
+    set_date(&env, 2024, 2, 15);
+    let reason = String::from_str(&env, "Promotion");
+    client.update_salary(&admin, &employee1, &3_000_000, &2024, &6, &reason);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::SalaryUpdated {
+            employee: employee1.clone(),
+            base_salary: 3_000_000,
+            effective_from: calendar::timestamp_from_date(2024, 6, 1).unwrap(),
+            reason: reason.clone(),
+        },
+    );
+    let change = client.update_salary(
+        &admin,
+        &employee1,
+        &2_400_000,
+        &2024,
+        &3,
+        &String::from_str(&env, "Market adjustment"),
+    );
+    assert_eq!(change.effective_from, calendar::timestamp_from_date(2024, 3, 1).unwrap());
+    assert_eq!(change.recorded_at, env.ledger().timestamp());
+
+    let history = client.get_compensation_history(&employee1);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get_unchecked(0), change);
+    assert_eq!(history.get_unchecked(1).base_salary, 3_000_000);
+    assert_eq!(history.get_unchecked(1).reason, reason);
+
+    // Paid months can't be changed; dates and amounts are validated
+    assert_eq!(
+        client.try_update_salary(&admin, &employee1, &2_000_000, &2024, &1, &reason),
+        Err(Ok(SalaryError::InvalidEffectiveDate))
+    );
+    assert_eq!(
+        client.try_update_salary(&admin, &employee1, &2_000_000, &2023, &12, &reason),
+        Err(Ok(SalaryError::InvalidEffectiveDate))
+    );
+    assert_eq!(
+        client.try_update_salary(&admin, &employee1, &2_000_000, &2024, &13, &reason),
+        Err(Ok(SalaryError::InvalidEffectiveDate))
+    );
+    // Years outside the calendar's range are rejected rather than converted
+    for year in [1969, 0, u64::MAX] {
+        assert_eq!(
+            client.try_update_salary(&admin, &employee1, &2_000_000, &year, &7, &reason),
+            Err(Ok(SalaryError::InvalidEffectiveDate))
+        );
+    }
+    assert_eq!(
+        client.try_update_salary(&admin, &employee1, &0, &2024, &7, &reason),
+        Err(Ok(SalaryError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_update_salary(&employee1, &employee1, &2_000_000, &2024, &7, &reason),
+        Err(Ok(SalaryError::Unauthorized))
+    );
+
+    // The salary in force is used for each period being paid
+    assert_eq!(client.calculate_current_salary(&employee1), 100_000);
+    set_date(&env, 2024, 2, 29);
+    client.claim_salary(&employee1);
+    assert_eq!(token_client.balance(&employee1), 200_000);
+
+    set_date(&env, 2024, 6, 30);
+    assert_eq!(client.calculate_current_salary(&employee1), 250_000);
+    assert_eq!(
+        client.get_arrears(&employee1),
+        Arrears {
+            periods: 4,
+            amount: 200_000 * 3 + 250_000,
+        }
+    );
+    client.claim_salary(&employee1);
+    assert_eq!(token_client.balance(&employee1), 1_050_000);
+
+    // The promoted salary still gets its increments on the hire anniversary
+    set_date(&env, 2024, 12, 31);
+    assert_eq!(client.calculate_current_salary(&employee1), 250_000);
+    set_date(&env, 2025, 1, 15);
+    assert_eq!(client.calculate_current_salary(&employee1), 262_500);
+
+    // Streaming accrual switches rate when a change takes effect
+    assert_eq!(client.get_accrued(&employee2), 1_200_000 + 1_260_000 * 14 / 365);
+    set_date(&env, 2025, 2, 1);
+    client.withdraw_accrued(&employee2, &1);
+    assert_eq!(
+        client.try_update_salary(&admin, &employee2, &2_400_000, &2025, &1, &reason),
+        Err(Ok(SalaryError::InvalidEffectiveDate))
+    );
+    client.update_salary(&admin, &employee2, &2_400_000, &2025, &7, &reason);
+    set_date(&env, 2026, 1, 1);
+    assert_eq!(
+        client.get_accrued(&employee2),
+        1_200_000 + 1_260_000 * 31 / 365 - 1 + 1_260_000 * 150 / 365 + 2_400_000 * 184 / 365
+    );
+    assert_eq!(client.calculate_current_salary(&employee1), 275_625);
+}
+
+#[test]
+fn test_per_employee_increment_policy() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let fixed_term = Address::generate(&env);
+    let staff = Address::generate(&env);
+    let executive = Address::generate(&env);
+    let default = Address::generate(&env);
+    let streaming = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, _) = create_token_contract(&env, &token_admin);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2024, 3, 15);
+    client.initialize(&admin, &500, &token_client.address);
+    for employee in [&fixed_term, &staff, &executive, &default, &streaming] {
+        client.add_employee(&admin, employee, &String::from_str(&env, "Employee"), &1_200_000);
+    }
+    client.enable_streaming(&admin, &streaming);
+
+    client.set_increment_policy(
+        &admin,
+        &fixed_term,
+        &Some(IncrementPolicy {
+            rate: 0,
+            method: IncrementMethod::Compound,
+            cap: 0,
+            basis: IncrementBasis::CalendarYear,
+        }),
+    );
+    let staff_policy = IncrementPolicy {
+        rate: 1000,
+        method: IncrementMethod::Simple,
+        cap: 1_500_000,
+        basis: IncrementBasis::CalendarYear,
+    };
+    client.set_increment_policy(&admin, &staff, &Some(staff_policy.clone()));
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::IncrementPolicyChanged {
+            employee: staff.clone(),
+            policy: staff_policy.clone(),
+        },
+    );
+    let executive_policy = IncrementPolicy {
+        rate: 700,
+        method: IncrementMethod::Compound,
+        cap: 0,
+        basis: IncrementBasis::Anniversary,
+    };
+    client.set_increment_policy(&admin, &executive, &Some(executive_policy.clone()));
+    client.set_increment_policy(
+        &admin,
+        &streaming,
+        &Some(IncrementPolicy {
+            rate: 1000,
+            method: IncrementMethod::Compound,
+            cap: 0,
+            basis: IncrementBasis::Anniversary,
+        }),
+    );
+
+    assert_eq!(client.get_increment_policy(&staff), staff_policy);
+    let default_policy = IncrementPolicy {
+        rate: 500,
+        method: IncrementMethod::Compound,
+        cap: 0,
+        basis: IncrementBasis::Anniversary,
+    };
+    assert_eq!(client.get_increment_policy(&default), default_policy);
+
+    assert_eq!(
+        client.try_set_increment_policy(&staff, &staff, &None),
+        Err(Ok(SalaryError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_increment_policy(
+            &admin,
+            &staff,
+            &Some(IncrementPolicy {
+                cap: -1,
+                ..staff_policy.clone()
+            })
+        ),
+        Err(Ok(SalaryError::InvalidAmount))
+    );
+
+    // Calendar-year policies step up in January, anniversary ones on the
+    // hire anniversary
+    set_date(&env, 2025, 1, 10);
+    assert_eq!(client.calculate_current_salary(&fixed_term), 100_000);
+    assert_eq!(client.calculate_current_salary(&staff), 110_000);
+    assert_eq!(client.calculate_current_salary(&executive), 100_000);
+    assert_eq!(client.calculate_current_salary(&default), 100_000);
+
+    set_date(&env, 2025, 3, 15);
+    assert_eq!(client.calculate_current_salary(&executive), 107_000);
+    assert_eq!(client.calculate_current_salary(&default), 105_000);
+
+    set_date(&env, 2025, 7, 1);
+    assert_eq!(
+        client.get_accrued(&streaming),
+        1_200_000 * 292 / 366 + 1_200_000 * 73 / 365 + 1_320_000 * 108 / 365
+    );
+
+    // Simple increments stop at the cap
+    set_date(&env, 2027, 1, 10);
+    assert_eq!(client.calculate_current_salary(&fixed_term), 100_000);
+    assert_eq!(client.calculate_current_salary(&staff), 125_000);
+    assert_eq!(client.calculate_current_salary(&executive), 1_373_880 / 12);
+    assert_eq!(client.calculate_current_salary(&default), 110_250);
+
+    // Clearing the override falls back to the global increment
+    client.set_increment_policy(&admin, &executive, &None);
+    assert_last_event(
+        &env,
+        &contract_id,
+        events::IncrementPolicyChanged {
+            employee: executive.clone(),
+            policy: default_policy,
+        },
+    );
+    assert_eq!(client.calculate_current_salary(&executive), 110_250);
+}
+
+#[test]
+fn test_period_with_anniversary_is_split() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &10_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2024, 12, 2);
+    client.initialize(&admin, &1000, &token_client.address);
+    client.fund_treasury(&funder, &token_client.address, &5_000_000);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Erin"), &1_200_000);
+
+    client.pay_salary(&admin, &employee1);
+    assert_eq!(token_client.balance(&employee1), 100_000 * 30 / 31);
+
+    set_date(&env, 2025, 11, 20);
+    client.pay_salary(&admin, &employee1);
+    assert_eq!(token_client.balance(&employee1), 100_000 * 30 / 31 + 1_100_000);
+
+    // December 1st is paid at the old salary, the rest of the month at the
+    // raise that took effect on the anniversary
+    set_date(&env, 2025, 12, 20);
+    assert_eq!(client.calculate_current_salary(&employee1), 110_000);
+    let before = token_client.balance(&employee1);
+    client.pay_salary(&admin, &employee1);
+    assert_eq!(
+        token_client.balance(&employee1) - before,
+        100_000 / 31 + 110_000 * 30 / 31
+    );
+    assert_eq!(token_client.balance(&employee1) - before, 109_676);
+
+    advance_months(&env, 1);
+    let before = token_client.balance(&employee1);
+    client.pay_salary(&admin, &employee1);
+    assert_eq!(token_client.balance(&employee1) - before, 110_000);
+}
+
+#[test]
+fn test_anniversary_increments() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let december_hire = Address::generate(&env);
+    let january_hire = Address::generate(&env);
+    let leap_day_hire = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, _) = create_token_contract(&env, &token_admin);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2024, 12, 15);
+    client.initialize(&admin, &1000, &token_client.address);
+    let name = String::from_str(&env, "Employee");
+    client.add_employee(&admin, &december_hire, &name, &1_200_000);
+
+    set_date(&env, 2025, 1, 31);
+    client.add_employee(&admin, &january_hire, &name, &1_200_000);
+
+    // A December hire gets no raise in January
+    assert_eq!(client.calculate_current_salary(&december_hire), 100_000);
+    set_date(&env, 2025, 12, 14);
+    assert_eq!(client.calculate_current_salary(&december_hire), 100_000);
+    set_date(&env, 2025, 12, 15);
+    assert_eq!(client.calculate_current_salary(&december_hire), 110_000);
+
+    // A January hire waits a full year too, not until the next calendar year
+    set_date(&env, 2026, 1, 30);
+    assert_eq!(client.calculate_current_salary(&january_hire), 100_000);
+    set_date(&env, 2026, 1, 31);
+    assert_eq!(client.calculate_current_salary(&january_hire), 110_000);
+
+    // A February 29th hire's anniversary is February 28th in common years
+    set_date(&env, 2028, 2, 29);
+    client.add_employee(&admin, &leap_day_hire, &name, &1_200_000);
+    set_date(&env, 2029, 2, 27);
+    assert_eq!(client.calculate_current_salary(&leap_day_hire), 100_000);
+    set_date(&env, 2029, 2, 28);
+    assert_eq!(client.calculate_current_salary(&leap_day_hire), 110_000);
+    set_date(&env, 2032, 2, 28);
+    assert_eq!(client.calculate_current_salary(&leap_day_hire), 133_100);
+    set_date(&env, 2032, 2, 29);
+    assert_eq!(client.calculate_current_salary(&leap_day_hire), 146_410);
+
+    let leap_day = calendar::timestamp_from_date(2028, 2, 29).unwrap();
+    assert_eq!(
+        calendar::anniversary(leap_day, 1).unwrap(),
+        calendar::timestamp_from_date(2029, 2, 28).unwrap()
+    );
+    assert_eq!(calendar::anniversary(leap_day, 4).unwrap(), leap_day + 1461 * calendar::SECONDS_PER_DAY);
+    assert_eq!(
+        calendar::years_between(leap_day, calendar::timestamp_from_date(2031, 2, 27).unwrap()),
+        2
+    );
+    assert_eq!(calendar::years_between(leap_day, leap_day - 1), 0);
+}
+
+/// Deterministic xorshift sequence for sampling operands
+fn next_random(state: &mut u128) -> u128 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+#[test]
+fn test_fixed_point_math_matches_reference() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+
+    // Exact 256-bit reference, rounded down
+    let reference = |a: u128, b: u128, denominator: u128| {
+        U256::from_u128(&env, a)
+            .mul(&U256::from_u128(&env, b))
+            .div(&U256::from_u128(&env, denominator))
+            .to_u128()
+    };
+
+    let edges = [
+        0,
+        1,
+        2,
+        3,
+        9_999,
+        10_000,
+        10_001,
+        fixed::SCALE - 1,
+        fixed::SCALE,
+        fixed::SCALE + 1,
+        u64::MAX as u128,
+        u64::MAX as u128 + 1,
+        1 << 100,
+        i128::MAX as u128,
+        u128::MAX - 1,
+        u128::MAX,
+    ];
+    for a in edges {
+        for b in edges {
+            assert_eq!(fixed::mul_div_floor(a, b, 0), None);
+            for denominator in edges.into_iter().filter(|&d| d != 0) {
+                assert_eq!(
+                    fixed::mul_div_floor(a, b, denominator),
+                    reference(a, b, denominator),
+                    "{} * {} / {}",
+                    a,
+                    b,
+                    denominator
+                );
+            }
+        }
+    }
+
+    let mut state = 0x2545_f491_4f6c_dd1d;
+    for _ in 0..2_000 {
+        let a = next_random(&mut state) >> (next_random(&mut state) % 128);
+        let b = next_random(&mut state) >> (next_random(&mut state) % 128);
+        let denominator = (next_random(&mut state) >> (next_random(&mut state) % 128)).max(1);
+        assert_eq!(fixed::mul_div_floor(a, b, denominator), reference(a, b, denominator));
+    }
+
+    // Basis points round toward zero and saturate
+    for amount in [0i128, 1, 99, 10_001, 123_456_789, -5_555, 10i128.pow(30)] {
+        for basis_points in 0..=BASIS_POINTS {
+            assert_eq!(
+                fixed::apply_bps(amount, basis_points),
+                amount * basis_points as i128 / 10_000
+            );
+        }
+    }
+    assert_eq!(fixed::apply_bps(i128::MAX, 10_000), i128::MAX);
+    assert_eq!(fixed::apply_bps(i128::MAX, 20_000), i128::MAX);
+    assert_eq!(fixed::apply_bps(i128::MIN + 1, 5_000), (i128::MIN + 1) / 2);
+
+    // Powers by squaring stay within a few units of the 18th decimal
+    for rate in [1u32, 250, 500, 2_500, 10_000] {
+        for exponent in 0..=13u32 {
+            let exact = U256::from_u32(&env, BASIS_POINTS + rate)
+                .pow(exponent)
+                .mul(&U256::from_u128(&env, fixed::SCALE))
+                .div(&U256::from_u32(&env, BASIS_POINTS).pow(exponent))
+                .to_u128()
+                .unwrap();
+            let power = fixed::pow(fixed::growth_factor(rate), exponent as u64);
+            assert!(power <= exact && exact - power <= 2 * exponent as u128 * exact / fixed::SCALE);
+        }
+    }
+    assert_eq!(fixed::pow(fixed::growth_factor(10_000), 10), 1024 * fixed::SCALE);
+    assert_eq!(fixed::pow(fixed::growth_factor(10_000), 200), u128::MAX);
+    assert_eq!(fixed::pow(0, 0), fixed::SCALE);
+
+    assert_eq!(fixed::growth_factor(0), fixed::SCALE);
+    assert_eq!(fixed::growth_factor(250), 1_025_000_000_000_000_000);
+    assert_eq!(fixed::mul(fixed::SCALE / 2, fixed::SCALE / 3), 166_666_666_666_666_666);
+    assert_eq!(fixed::mul(u128::MAX, 2 * fixed::SCALE), u128::MAX);
+    assert_eq!(fixed::scale(-7, fixed::SCALE / 2), -3);
+}
+
+#[test]
+fn test_increments_match_reference() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+
+    for rate in [0u32, 1, 25, 250, 333, 500, 700, 1_000, 2_500, 10_000] {
+        for method in [IncrementMethod::Compound, IncrementMethod::Simple] {
+            let policy = IncrementPolicy {
+                rate,
+                method,
+                cap: 0,
+                basis: IncrementBasis::Anniversary,
+            };
+            for base_salary in [1i128, 99_999, 1_200_000, 1_000_000_000_000] {
+                for increments in 0..=14u32 {
+                    let salary = policy.apply(base_salary, increments as u64);
+
+                    // base * (1 + rate)^n, or base * (1 + n * rate), rounded down
+                    let base = U256::from_u128(&env, base_salary as u128);
+                    let denominator = U256::from_u32(&env, BASIS_POINTS);
+                    let exact = match method {
+                        IncrementMethod::Compound => base
+                            .mul(&U256::from_u32(&env, BASIS_POINTS + rate).pow(increments))
+                            .div(&denominator.pow(increments)),
+                        IncrementMethod::Simple => base
+                            .mul(&U256::from_u32(&env, BASIS_POINTS + rate * increments))
+                            .div(&denominator),
+                    }
+                    .to_u128()
+                    .unwrap() as i128;
+
+                    // Compound factors are rounded to 18 decimals: at most one
+                    // unit below the exact value
+                    assert!(
+                        salary <= exact && exact - salary <= 1,
+                        "{} bp {:?} on {} for {} years: {} vs {}",
+                        rate,
+                        method,
+                        base_salary,
+                        increments,
+                        salary,
+                        exact
+                    );
+                    if method == IncrementMethod::Simple {
+                        assert_eq!(salary, exact);
+                    }
+                }
+            }
+        }
+    }
+
+    // A 2.5% global increment
+    let admin = Address::generate(&env);
+    let employee1 = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, _) = create_token_contract(&env, &token_admin);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    set_date(&env, 2024, 1, 1);
+    client.initialize(&admin, &250, &token_client.address);
+    client.add_employee(&admin, &employee1, &String::from_str(&env, "Alice"), &1_200_000);
+    set_date(&env, 2026, 1, 1);
+    assert_eq!(client.calculate_current_salary(&employee1), 1_260_750 / 12);
+}
+
+#[test]
+fn test_sustainability_budget_with_500_employees() {
+    // A snapshot of 500 employees would be several megabytes
+    let env = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &1_000_000_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2024, 1, 1);
+    client.initialize(&admin, &500, &token_client.address);
+    for _ in 0..500 {
+        client.add_employee(
+            &admin,
+            &Address::generate(&env),
+            &String::from_str(&env, "Employee"),
+            &1_200_000,
+        );
+    }
+
+    // 600M, 630M, 661.5M, ... a year: 5 years cost 3,315,378,500, 6 years
+    // 4,081,147,000
+    client.fund_treasury(&funder, &token_client.address, &3_400_000_000);
+
+    // Every batch fits in the default transaction budget, and only the
+    // covered years are carried from one batch to the next
+    let mut projection = None;
+    let mut batches = 0;
+    loop {
+        env.cost_estimate().budget().reset_default();
+        let next = client.project_sustainability(&50, &projection);
+        assert!(env.cost_estimate().budget().cpu_instruction_cost() < 100_000_000);
+        batches += 1;
+        if next.next_cursor.is_none() {
+            assert_eq!(next.years, 5);
+            assert_eq!(next.costs.len(), 5);
+            assert_eq!(next.costs.get(4).unwrap(), vec![&env, 729_303_500]);
+            break;
+        }
+        projection = Some(next);
+    }
+    assert_eq!(batches, 10);
+}
+
+#[test]
+fn test_sustainability_budget_small_roster() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let (token_client, token_admin_client) = create_token_contract(&env, &token_admin);
+
+    token_admin_client.mint(&funder, &1_000_000_000_000);
+
+    let contract_id = env.register(SalarySystemContract, ());
+    let client = SalarySystemContractClient::new(&env, &contract_id);
+
+    set_date(&env, 2024, 1, 1);
+    client.initialize(&admin, &500, &token_client.address);
+    for _ in 0..50 {
+        client.add_employee(
+            &admin,
+            &Address::generate(&env),
+            &String::from_str(&env, "Employee"),
+            &1_200_000,
+        );
+    }
+
+    // Rescanning every employee each simulated year cost 8,423,068 CPU
+    // instructions here with an empty treasury, and 43,226,120 for 8 years
+    env.cost_estimate().budget().reset_default();
+    assert_eq!(client.calculate_sustainability_years(), 0);
+    assert!(env.cost_estimate().budget().cpu_instruction_cost() < 8_423_068);
+
+    client.fund_treasury(&funder, &token_client.address, &600_000_000);
+    env.cost_estimate().budget().reset_default();
+    assert_eq!(client.calculate_sustainability_years(), 8);
+    assert!(env.cost_estimate().budget().cpu_instruction_cost() < 10_000_000);
 }